@@ -3,20 +3,54 @@ use fuel_core_inspector::{
     cli::{
         CommandWithoutConfig,
         FuelCoreInspectorCliArgs,
+        ValidatedCommand,
     },
     databases::DatabaseHandle,
     printer::PrintHexExt,
 };
 
 fn main() -> anyhow::Result<()> {
-    let validated_args = FuelCoreInspectorCliArgs::parse().validate()?;
+    let validated_args = match FuelCoreInspectorCliArgs::parse().validate()? {
+        ValidatedCommand::KeyValue(validated_args) => validated_args,
+        ValidatedCommand::VerifyBalances(args) => return args.run(),
+        ValidatedCommand::VerifyCoinsToSpend(args) => return args.run(),
+        ValidatedCommand::VerifyMessages(args) => return args.run(),
+        ValidatedCommand::VerifyBlockIds(args) => return args.run(),
+        ValidatedCommand::VerifyTransactions(args) => return args.run(),
+        ValidatedCommand::Tx(args) => return args.run(),
+        ValidatedCommand::Block(args) => return args.run(),
+        ValidatedCommand::Owner(args) => return args.run(),
+        ValidatedCommand::Contract(args) => return args.run(),
+        ValidatedCommand::Extract(args) => return args.run(),
+        ValidatedCommand::ConsensusParameters(args) => return args.run(),
+        ValidatedCommand::StateTransitionBytecodes(args) => return args.run(),
+        ValidatedCommand::Genesis(args) => return args.run(),
+        ValidatedCommand::Statistic(args) => return args.run(),
+        ValidatedCommand::Assets(args) => return args.run(),
+        ValidatedCommand::VerifyAssets(args) => return args.run(),
+        ValidatedCommand::GasPrice(args) => return args.run(),
+        ValidatedCommand::Relayer(args) => return args.run(),
+        ValidatedCommand::VerifyRelayerMessages(args) => return args.run(),
+        ValidatedCommand::RelayedTransactions(args) => return args.run(),
+        ValidatedCommand::Registry(args) => return args.run(),
+        ValidatedCommand::VerifyCompressedBlock(args) => return args.run(),
+        ValidatedCommand::Evictor(args) => return args.run(),
+        ValidatedCommand::Rollback(args) => return args.run(),
+    };
 
     let mut database_handle = DatabaseHandle::try_from(&validated_args)?;
 
     match validated_args.cmd() {
         CommandWithoutConfig::Inspect => {
-            let value = database_handle
-                .perform_read(validated_args.column(), validated_args.key())?;
+            let value = match validated_args.at_height() {
+                Some(height) => database_handle.perform_read_at(
+                    validated_args.column(),
+                    validated_args.key(),
+                    height,
+                )?,
+                None => database_handle
+                    .perform_read(validated_args.column(), validated_args.key())?,
+            };
             value.print();
         }
         CommandWithoutConfig::Mutate => {
@@ -0,0 +1,116 @@
+//! Chain-wide values derived from the stored blocks and consensus parameters
+
+use std::collections::BTreeMap;
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::old::OldFuelBlocks,
+    types::{
+        blockchain::{
+            header::BlockHeader,
+            primitives::DaBlockHeight,
+        },
+        fuel_tx::ConsensusParameters,
+        fuel_types::{
+            AssetId,
+            BlockHeight,
+            ChainId,
+        },
+    },
+};
+use fuel_core_storage::{
+    iter::{
+        IterDirection,
+        IteratorOverTable,
+    },
+    tables::{
+        ConsensusParametersVersions,
+        FuelBlocks,
+    },
+};
+
+use crate::databases::DatabaseHandle;
+
+/// Latest version of `ConsensusParametersVersions`
+pub(crate) fn latest_consensus_parameters(
+    database_handle: &DatabaseHandle,
+) -> anyhow::Result<ConsensusParameters> {
+    let (_, consensus_parameters) = database_handle
+        .database()
+        .on_chain()
+        .iter_all::<ConsensusParametersVersions>(Some(IterDirection::Reverse))
+        .next()
+        .transpose()?
+        .ok_or_else(|| anyhow::anyhow!("No consensus parameters found"))?;
+
+    Ok(consensus_parameters)
+}
+
+/// Chain id from the latest consensus parameters
+pub(crate) fn chain_id(database_handle: &DatabaseHandle) -> anyhow::Result<ChainId> {
+    Ok(latest_consensus_parameters(database_handle)?.chain_id())
+}
+
+/// Base asset id from the latest consensus parameters
+pub(crate) fn base_asset_id(database_handle: &DatabaseHandle) -> anyhow::Result<AssetId> {
+    Ok(*latest_consensus_parameters(database_handle)?.base_asset_id())
+}
+
+/// Find the first stored block, in `OldFuelBlocks` or `FuelBlocks`, using each version
+/// returned by `version_of`
+pub(crate) fn first_heights(
+    database_handle: &DatabaseHandle,
+    version_of: impl Fn(&BlockHeader) -> u32,
+) -> anyhow::Result<BTreeMap<u32, BlockHeight>> {
+    let mut first_heights = BTreeMap::new();
+
+    for entry in database_handle
+        .database()
+        .off_chain()
+        .iter_all::<OldFuelBlocks>(None)
+    {
+        let (height, block) = entry?;
+        first_heights
+            .entry(version_of(block.header()))
+            .or_insert(height);
+    }
+
+    for entry in database_handle
+        .database()
+        .on_chain()
+        .iter_all::<FuelBlocks>(None)
+    {
+        let (height, block) = entry?;
+        first_heights
+            .entry(version_of(block.header()))
+            .or_insert(height);
+    }
+
+    Ok(first_heights)
+}
+
+/// DA height referenced by the latest block of `FuelBlocks`, or of `OldFuelBlocks`
+/// when the on-chain database has no blocks
+pub(crate) fn latest_block_da_height(
+    database_handle: &DatabaseHandle,
+) -> anyhow::Result<DaBlockHeight> {
+    let latest_block = match database_handle
+        .database()
+        .on_chain()
+        .iter_all::<FuelBlocks>(Some(IterDirection::Reverse))
+        .next()
+        .transpose()?
+    {
+        Some((_, block)) => Some(block),
+        None => database_handle
+            .database()
+            .off_chain()
+            .iter_all::<OldFuelBlocks>(Some(IterDirection::Reverse))
+            .next()
+            .transpose()?
+            .map(|(_, block)| block),
+    };
+
+    Ok(latest_block
+        .map(|block| block.header().da_height())
+        .unwrap_or_default())
+}
@@ -0,0 +1,11 @@
+//! Consistency checks between the on-chain state and the off-chain indexes
+
+pub mod assets;
+pub mod balances;
+pub mod block_ids;
+pub mod coins_to_spend;
+pub mod compressed_blocks;
+pub mod messages;
+pub mod relayer_messages;
+pub mod statistic;
+pub mod transactions;
@@ -0,0 +1,343 @@
+//! Verify the supplies in `AssetsInfo` by replaying the mint and burn receipts
+
+use std::collections::BTreeMap;
+
+use fuel_core::{
+    database::database_description::IndexationKind,
+    fuel_core_graphql_api::storage::{
+        assets::AssetsInfo,
+        old::OldFuelBlocks,
+        transactions::TransactionStatuses,
+    },
+    types::{
+        blockchain::block::CompressedBlock,
+        fuel_tx::{
+            ContractIdExt,
+            Receipt,
+        },
+        fuel_types::{
+            AssetId,
+            BlockHeight,
+            Bytes32,
+            ContractId,
+        },
+        services::transaction_status::TransactionExecutionStatus,
+    },
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::{
+        IterDirection,
+        IteratorOverTable,
+    },
+    tables::FuelBlocks,
+};
+
+use crate::databases::{
+    Database,
+    DatabaseConfig,
+    DatabaseHandle,
+};
+
+/// Verify assets command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyAssetsArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// First block height to replay, defaults to the first stored block
+    #[arg(long)]
+    from: Option<u32>,
+
+    /// Last block height to replay, defaults to the latest stored block
+    #[arg(long)]
+    to: Option<u32>,
+}
+
+impl VerifyAssetsArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the assets verification
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        if !database_handle
+            .database()
+            .off_chain()
+            .indexation_available(IndexationKind::AssetMetadata)?
+        {
+            database_handle.shutdown();
+            anyhow::bail!(
+                "Asset metadata indexation is not enabled for this off-chain database, fuel-core doesn't maintain the assets info"
+            );
+        }
+
+        let result = AssetsReport::compute(&database_handle, self.from, self.to)
+            .map(|report| report.print());
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// A problem with a single `AssetsInfo` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetViolation {
+    /// The recorded supply differs from the replayed one
+    SupplyMismatch {
+        /// id of the asset
+        asset_id: AssetId,
+        /// supply stored in `AssetsInfo`
+        recorded: u128,
+        /// supply replayed from the receipts
+        replayed: i128,
+    },
+    /// Receipts mint or burn the asset, but it has no entry
+    Missing {
+        /// id of the asset
+        asset_id: AssetId,
+        /// supply replayed from the receipts
+        replayed: i128,
+    },
+    /// The asset id isn't derived from the recorded contract id and sub id
+    WrongId {
+        /// id of the asset
+        asset_id: AssetId,
+        /// contract id stored in `AssetsInfo`
+        contract_id: ContractId,
+        /// sub id stored in `AssetsInfo`
+        sub_id: Bytes32,
+    },
+}
+
+impl core::fmt::Display for AssetViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::SupplyMismatch {
+                asset_id,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "supply mismatch: asset_id={asset_id} recorded={recorded} replayed={replayed}"
+            ),
+            Self::Missing { asset_id, replayed } => {
+                write!(f, "missing entry: asset_id={asset_id} replayed={replayed}")
+            }
+            Self::WrongId {
+                asset_id,
+                contract_id,
+                sub_id,
+            } => write!(
+                f,
+                "wrong id: asset_id={asset_id} contract_id={contract_id} sub_id={sub_id}"
+            ),
+        }
+    }
+}
+
+/// Result of replaying the mint and burn receipts over a block range
+#[derive(Debug, Clone, Default)]
+pub struct AssetsReport {
+    /// number of replayed blocks
+    pub blocks: usize,
+    /// number of replayed successful transactions
+    pub transactions: usize,
+    /// whether the replay covered every stored block, so the replayed supplies can be
+    /// compared with the recorded ones
+    pub full_replay: bool,
+    /// supply change of every asset minted or burned in the range, only for a partial
+    /// replay
+    pub deltas: BTreeMap<AssetId, i128>,
+    /// all problems found
+    pub violations: Vec<AssetViolation>,
+}
+
+impl AssetsReport {
+    /// Replay the mint and burn receipts of the successful transactions in the blocks
+    /// of `OldFuelBlocks` and `FuelBlocks` between `from` and `to`, and compare the
+    /// supplies with `AssetsInfo`.
+    ///
+    /// `AssetsInfo` holds the supplies accumulated since the first block, so they are
+    /// only compared when the replay covers every stored block. Any other range only
+    /// reports the supply change of each asset, which isn't a violation.
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        from: Option<u32>,
+        to: Option<u32>,
+    ) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let start = from.map(BlockHeight::from);
+        let in_range = |height: &BlockHeight| to.is_none_or(|to| **height <= to);
+
+        let first_height = match off_chain
+            .iter_all_keys::<OldFuelBlocks>(Some(IterDirection::Forward))
+            .next()
+            .transpose()?
+        {
+            Some(height) => Some(height),
+            None => on_chain
+                .iter_all_keys::<FuelBlocks>(Some(IterDirection::Forward))
+                .next()
+                .transpose()?,
+        };
+        let latest_height = on_chain
+            .iter_all_keys::<FuelBlocks>(Some(IterDirection::Reverse))
+            .next()
+            .transpose()?;
+        let from_first =
+            start.is_none_or(|start| first_height.is_none_or(|first| start <= first));
+        let to_latest =
+            to.is_none_or(|to| latest_height.is_none_or(|latest| to >= *latest));
+
+        let mut report = Self {
+            full_replay: from_first && to_latest,
+            ..Default::default()
+        };
+        let mut supplies = BTreeMap::<AssetId, i128>::new();
+
+        let mut replay_block = |block: CompressedBlock| -> anyhow::Result<()> {
+            for tx_id in block.transactions() {
+                let status = off_chain
+                    .storage::<TransactionStatuses>()
+                    .get(tx_id)?
+                    .map(|status| status.into_owned());
+                let Some(TransactionExecutionStatus::Success { receipts, .. }) = status
+                else {
+                    continue;
+                };
+
+                for receipt in receipts {
+                    let (contract_id, sub_id, amount, minted) = match receipt {
+                        Receipt::Mint {
+                            contract_id,
+                            sub_id,
+                            val,
+                            ..
+                        } => (contract_id, sub_id, i128::from(val), true),
+                        Receipt::Burn {
+                            contract_id,
+                            sub_id,
+                            val,
+                            ..
+                        } => (contract_id, sub_id, i128::from(val), false),
+                        _ => continue,
+                    };
+                    let supply =
+                        supplies.entry(contract_id.asset_id(&sub_id)).or_default();
+                    *supply = if minted {
+                        supply.saturating_add(amount)
+                    } else {
+                        supply.saturating_sub(amount)
+                    };
+                }
+                report.transactions = report.transactions.saturating_add(1);
+            }
+            report.blocks = report.blocks.saturating_add(1);
+            Ok(())
+        };
+
+        for entry in off_chain.iter_all_filtered::<OldFuelBlocks, [u8; 0]>(
+            None,
+            start.as_ref(),
+            Some(IterDirection::Forward),
+        ) {
+            let (height, block) = entry?;
+            if !in_range(&height) {
+                break;
+            }
+            replay_block(block)?;
+        }
+
+        for entry in on_chain.iter_all_filtered::<FuelBlocks, [u8; 0]>(
+            None,
+            start.as_ref(),
+            Some(IterDirection::Forward),
+        ) {
+            let (height, block) = entry?;
+            if !in_range(&height) {
+                break;
+            }
+            replay_block(block)?;
+        }
+
+        for (asset_id, replayed) in &supplies {
+            match off_chain.storage::<AssetsInfo>().get(asset_id)? {
+                Some(_) if !report.full_replay => {}
+                Some(details)
+                    if i128::try_from(details.total_supply).ok() != Some(*replayed) =>
+                {
+                    report.violations.push(AssetViolation::SupplyMismatch {
+                        asset_id: *asset_id,
+                        recorded: details.total_supply,
+                        replayed: *replayed,
+                    })
+                }
+                Some(_) => {}
+                None => report.violations.push(AssetViolation::Missing {
+                    asset_id: *asset_id,
+                    replayed: *replayed,
+                }),
+            }
+        }
+
+        for entry in off_chain.iter_all::<AssetsInfo>(None) {
+            let (asset_id, details) = entry?;
+            if details.contract_id.asset_id(&details.sub_id) != asset_id {
+                report.violations.push(AssetViolation::WrongId {
+                    asset_id,
+                    contract_id: details.contract_id,
+                    sub_id: details.sub_id,
+                });
+            }
+            if report.full_replay
+                && !supplies.contains_key(&asset_id)
+                && details.total_supply != 0
+            {
+                report.violations.push(AssetViolation::SupplyMismatch {
+                    asset_id,
+                    recorded: details.total_supply,
+                    replayed: 0,
+                });
+            }
+        }
+
+        if !report.full_replay {
+            report.deltas = supplies;
+        }
+
+        Ok(report)
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for violation in &self.violations {
+            println!("{violation}");
+        }
+        for (asset_id, delta) in &self.deltas {
+            println!("supply delta: asset_id={asset_id} delta={delta}");
+        }
+        if !self.full_replay {
+            println!(
+                "Replay didn't cover every stored block, only the supply deltas of the range are reported"
+            );
+        }
+        println!(
+            "Replayed {} block(s) and {} transaction(s), found {} problem(s)",
+            self.blocks,
+            self.transactions,
+            self.violations.len()
+        );
+    }
+}
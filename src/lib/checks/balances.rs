@@ -0,0 +1,393 @@
+//! Recompute and verify `CoinBalances` and `MessageBalances`
+
+use std::collections::BTreeMap;
+
+use fuel_core::{
+    database::database_description::IndexationKind,
+    fuel_core_graphql_api::storage::{
+        balances::{
+            CoinBalances,
+            CoinBalancesKey,
+            MessageBalance,
+            MessageBalances,
+            TotalBalanceAmount,
+        },
+        messages::SpentMessages,
+    },
+    types::fuel_types::{
+        Address,
+        AssetId,
+    },
+};
+use fuel_core_storage::{
+    StorageAsMut,
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::{
+        Coins,
+        Messages,
+    },
+    transactional::ReadTransaction,
+};
+
+use crate::databases::{
+    Database,
+    DatabaseConfig,
+    DatabaseHandle,
+};
+
+/// Verify balances command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyBalancesArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Rewrite the stored balances with the recomputed ones
+    #[arg(long)]
+    rewrite: bool,
+}
+
+impl VerifyBalancesArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the balances verification
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        if !database_handle
+            .database()
+            .off_chain()
+            .indexation_available(IndexationKind::Balances)?
+        {
+            database_handle.shutdown();
+            anyhow::bail!(
+                "Balances indexation is not enabled for this off-chain database, fuel-core doesn't maintain the balances"
+            );
+        }
+
+        let report = BalancesReport::compute(&database_handle)?;
+        report.print();
+
+        if self.rewrite && !report.mismatches.is_empty() {
+            report.rewrite(&database_handle)?;
+            println!("Rewrote {} balance(s)", report.mismatches.len());
+        }
+
+        database_handle.shutdown();
+
+        Ok(())
+    }
+}
+
+/// A difference between the stored and the recomputed balance
+#[derive(Debug, Clone, PartialEq)]
+pub enum BalanceMismatch {
+    /// Coin balance of `owner` for `asset_id` differs
+    Coin {
+        /// owner of the coins
+        owner: Address,
+        /// asset of the coins
+        asset_id: AssetId,
+        /// balance stored in `CoinBalances`
+        stored: Option<TotalBalanceAmount>,
+        /// balance recomputed from `Coins`
+        computed: TotalBalanceAmount,
+    },
+    /// Message balance of `owner` differs
+    Message {
+        /// recipient of the messages
+        owner: Address,
+        /// balance stored in `MessageBalances`
+        stored: Option<MessageBalance>,
+        /// balance recomputed from `Messages`
+        computed: MessageBalance,
+    },
+}
+
+impl core::fmt::Display for BalanceMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Coin {
+                owner,
+                asset_id,
+                stored,
+                computed,
+            } => write!(
+                f,
+                "coin balance mismatch: owner={owner} asset_id={asset_id} stored={stored:?} computed={computed}"
+            ),
+            Self::Message {
+                owner,
+                stored,
+                computed,
+            } => write!(
+                f,
+                "message balance mismatch: owner={owner} stored={stored:?} computed={computed:?}"
+            ),
+        }
+    }
+}
+
+/// Result of diffing the stored balances against the recomputed ones
+#[derive(Debug, Clone, Default)]
+pub struct BalancesReport {
+    /// number of distinct (owner, asset) coin balances seen
+    pub coin_balances: usize,
+    /// number of distinct message balance owners seen
+    pub message_balances: usize,
+    /// all mismatching balances
+    pub mismatches: Vec<BalanceMismatch>,
+}
+
+impl BalancesReport {
+    /// Recompute balances from the on-chain `Coins` and `Messages`
+    /// and diff them against the stored aggregates
+    pub fn compute(database_handle: &DatabaseHandle) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let mut computed_coins =
+            BTreeMap::<(Address, AssetId), TotalBalanceAmount>::new();
+        for entry in on_chain.iter_all::<Coins>(None) {
+            let (_, coin) = entry?;
+            let balance = computed_coins
+                .entry((*coin.owner(), *coin.asset_id()))
+                .or_default();
+            *balance = balance.saturating_add(u128::from(*coin.amount()));
+        }
+
+        let mut computed_messages = BTreeMap::<Address, MessageBalance>::new();
+        for entry in on_chain.iter_all::<Messages>(None) {
+            let (nonce, message) = entry?;
+            if off_chain.storage::<SpentMessages>().contains_key(&nonce)? {
+                continue;
+            }
+            let balance = computed_messages.entry(*message.recipient()).or_default();
+            let amount = u128::from(message.amount());
+            if message.is_retryable_message() {
+                balance.retryable = balance.retryable.saturating_add(amount);
+            } else {
+                balance.non_retryable = balance.non_retryable.saturating_add(amount);
+            }
+        }
+
+        let mut stored_coins = BTreeMap::new();
+        for entry in off_chain.iter_all::<CoinBalances>(None) {
+            let (key, amount) = entry?;
+            stored_coins.insert((*key.address(), *key.asset_id()), amount);
+        }
+
+        let mut stored_messages = BTreeMap::new();
+        for entry in off_chain.iter_all::<MessageBalances>(None) {
+            let (owner, balance) = entry?;
+            stored_messages.insert(owner, balance);
+        }
+
+        let mut report = Self::default();
+
+        let coin_keys = computed_coins
+            .keys()
+            .chain(stored_coins.keys())
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>();
+        report.coin_balances = coin_keys.len();
+        for (owner, asset_id) in coin_keys {
+            let stored = stored_coins.get(&(owner, asset_id)).copied();
+            let computed = computed_coins
+                .get(&(owner, asset_id))
+                .copied()
+                .unwrap_or_default();
+            if stored.unwrap_or_default() != computed {
+                report.mismatches.push(BalanceMismatch::Coin {
+                    owner,
+                    asset_id,
+                    stored,
+                    computed,
+                });
+            }
+        }
+
+        let message_keys = computed_messages
+            .keys()
+            .chain(stored_messages.keys())
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>();
+        report.message_balances = message_keys.len();
+        for owner in message_keys {
+            let stored = stored_messages.get(&owner).cloned();
+            let computed = computed_messages.get(&owner).cloned().unwrap_or_default();
+            if stored.clone().unwrap_or_default() != computed {
+                report.mismatches.push(BalanceMismatch::Message {
+                    owner,
+                    stored,
+                    computed,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Overwrite the mismatching stored balances with the recomputed ones
+    pub fn rewrite(&self, database_handle: &DatabaseHandle) -> anyhow::Result<()> {
+        let mut tx = database_handle.database().off_chain().read_transaction();
+
+        for mismatch in &self.mismatches {
+            match mismatch {
+                BalanceMismatch::Coin {
+                    owner,
+                    asset_id,
+                    computed,
+                    ..
+                } => {
+                    tx.storage_as_mut::<CoinBalances>()
+                        .insert(&CoinBalancesKey::new(owner, asset_id), computed)?;
+                }
+                BalanceMismatch::Message {
+                    owner, computed, ..
+                } => {
+                    tx.storage_as_mut::<MessageBalances>()
+                        .insert(owner, computed)?;
+                }
+            }
+        }
+
+        database_handle.commit_changes(Database::OffChain, tx.into_changes())
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for mismatch in &self.mismatches {
+            println!("{mismatch}");
+        }
+        println!(
+            "Checked {} coin balance(s) and {} message balance(s), found {} mismatch(es)",
+            self.coin_balances,
+            self.message_balances,
+            self.mismatches.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_core::{
+        database::{
+            database_description::{
+                DatabaseDescription,
+                DatabaseMetadata,
+                off_chain::OffChain,
+            },
+            metadata::MetadataTable,
+        },
+        types::{
+            entities::coins::coin::CompressedCoin,
+            fuel_tx::UtxoId,
+        },
+    };
+
+    use super::*;
+
+    fn config(dir: &tempfile::TempDir) -> DatabaseConfig {
+        DatabaseConfig {
+            path: dir.path().to_string_lossy().into_owned(),
+        }
+    }
+
+    fn open(dir: &tempfile::TempDir) -> DatabaseHandle {
+        DatabaseHandle::try_new_historical(
+            Database::OffChain,
+            &[Database::OnChain],
+            config(dir),
+        )
+        .unwrap()
+    }
+
+    fn coin_balance(dir: &tempfile::TempDir) -> Option<TotalBalanceAmount> {
+        let database_handle = open(dir);
+        let balance = database_handle
+            .database()
+            .off_chain()
+            .storage::<CoinBalances>()
+            .get(&CoinBalancesKey::new(
+                &Address::from([1; 32]),
+                &AssetId::BASE,
+            ))
+            .unwrap()
+            .map(|balance| *balance);
+        database_handle.shutdown();
+        balance
+    }
+
+    /// Two coins of 3 and 4 while the indexed balance says 5
+    fn wrong_balance(dir: &tempfile::TempDir) {
+        let database_handle = open(dir);
+
+        let mut tx = database_handle.database().on_chain().read_transaction();
+        for (output_index, amount) in [(0, 3), (1, 4)] {
+            let mut coin = CompressedCoin::default();
+            coin.set_owner(Address::from([1; 32]));
+            coin.set_asset_id(AssetId::BASE);
+            coin.set_amount(amount);
+            tx.storage_as_mut::<Coins>()
+                .insert(&UtxoId::new(Default::default(), output_index), &coin)
+                .unwrap();
+        }
+        database_handle
+            .commit_changes(Database::OnChain, tx.into_changes())
+            .unwrap();
+
+        let mut tx = database_handle.database().off_chain().read_transaction();
+        tx.storage_as_mut::<MetadataTable<OffChain>>()
+            .insert(
+                &(),
+                &DatabaseMetadata::V2 {
+                    version: OffChain::version(),
+                    height: Default::default(),
+                    indexation_availability: [IndexationKind::Balances].into(),
+                },
+            )
+            .unwrap();
+        tx.storage_as_mut::<CoinBalances>()
+            .insert(
+                &CoinBalancesKey::new(&Address::from([1; 32]), &AssetId::BASE),
+                &5,
+            )
+            .unwrap();
+        database_handle
+            .commit_changes(Database::OffChain, tx.into_changes())
+            .unwrap();
+
+        database_handle.shutdown();
+    }
+
+    #[test]
+    fn rewrite_replaces_the_wrong_balances() {
+        let dir = tempfile::tempdir().unwrap();
+        wrong_balance(&dir);
+
+        VerifyBalancesArgs {
+            database_config: config(&dir),
+            rewrite: false,
+        }
+        .run()
+        .unwrap();
+        assert_eq!(coin_balance(&dir), Some(5));
+
+        VerifyBalancesArgs {
+            database_config: config(&dir),
+            rewrite: true,
+        }
+        .run()
+        .unwrap();
+        assert_eq!(coin_balance(&dir), Some(7));
+    }
+}
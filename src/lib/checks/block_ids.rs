@@ -0,0 +1,341 @@
+//! Verify and repair the `FuelBlockIdsToHeights` index
+
+use std::collections::BTreeSet;
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::{
+        blocks::FuelBlockIdsToHeights,
+        old::OldFuelBlocks,
+    },
+    types::{
+        blockchain::{
+            block::CompressedBlock,
+            primitives::BlockId,
+        },
+        fuel_types::BlockHeight,
+    },
+};
+use fuel_core_storage::{
+    StorageAsMut,
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::FuelBlocks,
+    transactional::ReadTransaction,
+};
+
+use crate::databases::{
+    Database,
+    DatabaseConfig,
+    DatabaseHandle,
+};
+
+/// Verify block ids command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyBlockIdsArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Repair the index from the stored blocks
+    #[arg(long)]
+    repair: bool,
+}
+
+impl VerifyBlockIdsArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the block ids verification
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let report = BlockIdsReport::compute(&database_handle)?;
+        report.print();
+
+        if self.repair && !report.violations.is_empty() {
+            report.repair(&database_handle)?;
+            println!("Repaired {} entry(ies)", report.violations.len());
+        }
+
+        database_handle.shutdown();
+
+        Ok(())
+    }
+}
+
+/// A problem with a single `FuelBlockIdsToHeights` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockIdViolation {
+    /// The block has no entry
+    Missing {
+        /// id of the block
+        id: BlockId,
+        /// height of the block
+        height: BlockHeight,
+    },
+    /// The entry maps the block id to another height
+    Mismatched {
+        /// id of the block
+        id: BlockId,
+        /// height stored in the index
+        stored: BlockHeight,
+        /// height of the block
+        expected: BlockHeight,
+    },
+    /// The entry doesn't belong to any stored block
+    Extra {
+        /// id stored in the index
+        id: BlockId,
+        /// height stored in the index
+        height: BlockHeight,
+    },
+}
+
+impl core::fmt::Display for BlockIdViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Missing { id, height } => {
+                write!(f, "missing entry: id={id} height={}", **height)
+            }
+            Self::Mismatched {
+                id,
+                stored,
+                expected,
+            } => write!(
+                f,
+                "mismatched entry: id={id} stored={} expected={}",
+                **stored, **expected
+            ),
+            Self::Extra { id, height } => {
+                write!(f, "extra entry: id={id} height={}", **height)
+            }
+        }
+    }
+}
+
+/// Result of diffing `FuelBlockIdsToHeights` against the stored blocks
+#[derive(Debug, Clone, Default)]
+pub struct BlockIdsReport {
+    /// number of blocks in `FuelBlocks`
+    pub blocks: usize,
+    /// number of blocks in `OldFuelBlocks`
+    pub old_blocks: usize,
+    /// all problems found
+    pub violations: Vec<BlockIdViolation>,
+}
+
+impl BlockIdsReport {
+    /// Rebuild the id to height mapping from the on-chain `FuelBlocks` and the
+    /// off-chain `OldFuelBlocks` and diff it against the stored index
+    pub fn compute(database_handle: &DatabaseHandle) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let mut report = Self::default();
+        let mut mismatched = BTreeSet::new();
+
+        let mut check_block =
+            |height: BlockHeight, block: CompressedBlock| -> anyhow::Result<()> {
+                let id = block.header().id();
+                let stored = off_chain
+                    .storage::<FuelBlockIdsToHeights>()
+                    .get(&id)?
+                    .map(|height| *height);
+                match stored {
+                    None => report
+                        .violations
+                        .push(BlockIdViolation::Missing { id, height }),
+                    Some(stored) if stored != height => {
+                        mismatched.insert(id);
+                        report.violations.push(BlockIdViolation::Mismatched {
+                            id,
+                            stored,
+                            expected: height,
+                        })
+                    }
+                    Some(_) => {}
+                }
+                Ok(())
+            };
+
+        let mut blocks = 0usize;
+        for entry in on_chain.iter_all::<FuelBlocks>(None) {
+            let (height, block) = entry?;
+            check_block(height, block)?;
+            blocks = blocks.saturating_add(1);
+        }
+
+        let mut old_blocks = 0usize;
+        for entry in off_chain.iter_all::<OldFuelBlocks>(None) {
+            let (height, block) = entry?;
+            check_block(height, block)?;
+            old_blocks = old_blocks.saturating_add(1);
+        }
+
+        report.blocks = blocks;
+        report.old_blocks = old_blocks;
+
+        for entry in off_chain.iter_all::<FuelBlockIdsToHeights>(None) {
+            let (id, height) = entry?;
+            if mismatched.contains(&id) {
+                continue;
+            }
+            let block = match on_chain.storage::<FuelBlocks>().get(&height)? {
+                Some(block) => Some(block.into_owned()),
+                None => off_chain
+                    .storage::<OldFuelBlocks>()
+                    .get(&height)?
+                    .map(|block| block.into_owned()),
+            };
+            if block.is_none_or(|block| block.header().id() != id) {
+                report
+                    .violations
+                    .push(BlockIdViolation::Extra { id, height });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Insert the missing and mismatched entries and remove the extra ones
+    pub fn repair(&self, database_handle: &DatabaseHandle) -> anyhow::Result<()> {
+        let mut tx = database_handle.database().off_chain().read_transaction();
+
+        for violation in &self.violations {
+            match violation {
+                BlockIdViolation::Missing { id, height }
+                | BlockIdViolation::Mismatched {
+                    id,
+                    expected: height,
+                    ..
+                } => {
+                    tx.storage_as_mut::<FuelBlockIdsToHeights>()
+                        .insert(id, height)?;
+                }
+                BlockIdViolation::Extra { id, .. } => {
+                    tx.storage_as_mut::<FuelBlockIdsToHeights>().remove(id)?;
+                }
+            }
+        }
+
+        database_handle.commit_changes(Database::OffChain, tx.into_changes())
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for violation in &self.violations {
+            println!("{violation}");
+        }
+        println!(
+            "Checked {} block(s) and {} old block(s), found {} problem(s)",
+            self.blocks,
+            self.old_blocks,
+            self.violations.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn config(dir: &tempfile::TempDir) -> DatabaseConfig {
+        DatabaseConfig {
+            path: dir.path().to_string_lossy().into_owned(),
+        }
+    }
+
+    fn open(dir: &tempfile::TempDir) -> DatabaseHandle {
+        DatabaseHandle::try_new_historical(
+            Database::OffChain,
+            &[Database::OnChain],
+            config(dir),
+        )
+        .unwrap()
+    }
+
+    fn block(height: u32) -> CompressedBlock {
+        let mut block = CompressedBlock::default();
+        block.header_mut().set_block_height(height.into());
+        block
+    }
+
+    fn stored_index(dir: &tempfile::TempDir) -> BTreeMap<BlockId, BlockHeight> {
+        let database_handle = open(dir);
+        let index = database_handle
+            .database()
+            .off_chain()
+            .iter_all::<FuelBlockIdsToHeights>(None)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        database_handle.shutdown();
+        index
+    }
+
+    /// Blocks 1 and 2, where block 1 is indexed at height 5, block 2 isn't indexed
+    /// and an unknown id is indexed at height 7
+    fn broken_index(dir: &tempfile::TempDir) {
+        let database_handle = open(dir);
+
+        let mut tx = database_handle.database().on_chain().read_transaction();
+        for height in [1, 2] {
+            tx.storage_as_mut::<FuelBlocks>()
+                .insert(&height.into(), &block(height))
+                .unwrap();
+        }
+        database_handle
+            .commit_changes(Database::OnChain, tx.into_changes())
+            .unwrap();
+
+        let mut tx = database_handle.database().off_chain().read_transaction();
+        tx.storage_as_mut::<FuelBlockIdsToHeights>()
+            .insert(&block(1).header().id(), &5.into())
+            .unwrap();
+        tx.storage_as_mut::<FuelBlockIdsToHeights>()
+            .insert(&BlockId::from([9; 32]), &7.into())
+            .unwrap();
+        database_handle
+            .commit_changes(Database::OffChain, tx.into_changes())
+            .unwrap();
+
+        database_handle.shutdown();
+    }
+
+    #[test]
+    fn repair_rebuilds_the_index_from_the_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        broken_index(&dir);
+        let broken = stored_index(&dir);
+
+        VerifyBlockIdsArgs {
+            database_config: config(&dir),
+            repair: false,
+        }
+        .run()
+        .unwrap();
+        assert_eq!(stored_index(&dir), broken);
+
+        VerifyBlockIdsArgs {
+            database_config: config(&dir),
+            repair: true,
+        }
+        .run()
+        .unwrap();
+        assert_eq!(
+            stored_index(&dir),
+            [
+                (block(1).header().id(), 1.into()),
+                (block(2).header().id(), 2.into()),
+            ]
+            .into()
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Verify and regenerate the `CoinsToSpend` index
+
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+
+use fuel_core::{
+    database::database_description::IndexationKind,
+    fuel_core_graphql_api::storage::{
+        coins::{
+            CoinsToSpendIndex,
+            CoinsToSpendIndexKey,
+        },
+        messages::SpentMessages,
+    },
+    types::{
+        fuel_tx::UtxoId,
+        fuel_types::{
+            Address,
+            Nonce,
+        },
+    },
+};
+use fuel_core_storage::{
+    StorageAsMut,
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::{
+        Coins,
+        Messages,
+    },
+    transactional::ReadTransaction,
+};
+
+use crate::{
+    chain::base_asset_id,
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+};
+
+/// Verify coins to spend command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyCoinsToSpendArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Only verify the entries of this owner
+    #[arg(long)]
+    owner: Option<Address>,
+
+    /// Regenerate the index from the on-chain coins and messages
+    #[arg(long)]
+    regenerate: bool,
+}
+
+impl VerifyCoinsToSpendArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the coins to spend verification
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        if !database_handle
+            .database()
+            .off_chain()
+            .indexation_available(IndexationKind::CoinsToSpend)?
+        {
+            database_handle.shutdown();
+            anyhow::bail!(
+                "Coins to spend indexation is not enabled for this off-chain database, fuel-core doesn't maintain the index"
+            );
+        }
+
+        let report = CoinsToSpendReport::compute(&database_handle, self.owner.as_ref())?;
+        report.print();
+
+        if self.regenerate && !report.violations.is_empty() {
+            report.regenerate(&database_handle)?;
+            println!("Fixed {} problem(s)", report.violations.len());
+        }
+
+        database_handle.shutdown();
+
+        Ok(())
+    }
+}
+
+/// A problem with a single `CoinsToSpend` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoinsToSpendViolation {
+    /// The entry points at a coin or message which doesn't exist or is spent
+    Stale {
+        /// the stored index key
+        key: CoinsToSpendIndexKey,
+    },
+    /// The entry points at an existing coin or message, but its fields differ
+    Mismatched {
+        /// the stored index key
+        key: CoinsToSpendIndexKey,
+        /// the index key derived from the coin or message
+        expected: CoinsToSpendIndexKey,
+    },
+    /// An unspent coin or message has no entry
+    Missing {
+        /// the index key derived from the coin or message
+        expected: CoinsToSpendIndexKey,
+    },
+}
+
+impl core::fmt::Display for CoinsToSpendViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Stale { key } => {
+                write!(f, "stale entry: {} ({key})", describe(key))
+            }
+            Self::Mismatched { key, expected } => write!(
+                f,
+                "mismatched entry: {} stored=({key}) expected=({expected})",
+                describe(key)
+            ),
+            Self::Missing { expected } => {
+                write!(f, "missing entry: {} ({expected})", describe(expected))
+            }
+        }
+    }
+}
+
+fn describe(key: &CoinsToSpendIndexKey) -> String {
+    match key {
+        CoinsToSpendIndexKey::Coin { utxo_id, .. } => format!("coin {utxo_id}"),
+        CoinsToSpendIndexKey::Message { nonce, .. } => format!("message {nonce}"),
+    }
+}
+
+/// Result of diffing the stored `CoinsToSpend` index against the on-chain state
+#[derive(Debug, Clone, Default)]
+pub struct CoinsToSpendReport {
+    /// number of stored index entries checked
+    pub checked: usize,
+    /// all problems found
+    pub violations: Vec<CoinsToSpendViolation>,
+}
+
+impl CoinsToSpendReport {
+    /// Derive the expected index from the on-chain `Coins` and unspent `Messages`
+    /// and diff it against the stored index, optionally only for `owner`
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        owner: Option<&Address>,
+    ) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+        let is_owned = |address: &Address| owner.is_none_or(|owner| owner == address);
+
+        let base_asset_id = base_asset_id(database_handle)?;
+
+        let mut expected_coins = BTreeMap::<UtxoId, CoinsToSpendIndexKey>::new();
+        for entry in on_chain.iter_all::<Coins>(None) {
+            let (utxo_id, coin) = entry?;
+            if !is_owned(coin.owner()) {
+                continue;
+            }
+            let coin = coin.uncompress(utxo_id);
+            expected_coins.insert(utxo_id, CoinsToSpendIndexKey::from_coin(&coin));
+        }
+
+        let mut expected_messages = BTreeMap::<Nonce, CoinsToSpendIndexKey>::new();
+        for entry in on_chain.iter_all::<Messages>(None) {
+            let (nonce, message) = entry?;
+            if !is_owned(message.recipient())
+                || off_chain.storage::<SpentMessages>().contains_key(&nonce)?
+            {
+                continue;
+            }
+            expected_messages.insert(
+                nonce,
+                CoinsToSpendIndexKey::from_message(&message, &base_asset_id),
+            );
+        }
+
+        let mut stored = Vec::new();
+        for entry in off_chain.iter_all_keys::<CoinsToSpendIndex>(None) {
+            let key = entry?;
+            if is_owned(key.owner()) {
+                stored.push(key);
+            }
+        }
+
+        let mut report = Self {
+            checked: stored.len(),
+            ..Default::default()
+        };
+
+        // exact matches first, so that a duplicate entry doesn't hide a correct one
+        let mut unmatched = Vec::new();
+        for key in stored {
+            let expected = match &key {
+                CoinsToSpendIndexKey::Coin { utxo_id, .. } => expected_coins.get(utxo_id),
+                CoinsToSpendIndexKey::Message { nonce, .. } => {
+                    expected_messages.get(nonce)
+                }
+            };
+            if expected == Some(&key) {
+                match &key {
+                    CoinsToSpendIndexKey::Coin { utxo_id, .. } => {
+                        expected_coins.remove(utxo_id);
+                    }
+                    CoinsToSpendIndexKey::Message { nonce, .. } => {
+                        expected_messages.remove(nonce);
+                    }
+                }
+            } else {
+                unmatched.push(key);
+            }
+        }
+
+        for key in unmatched {
+            let expected = match &key {
+                CoinsToSpendIndexKey::Coin { utxo_id, .. } => {
+                    expected_coins.remove(utxo_id)
+                }
+                CoinsToSpendIndexKey::Message { nonce, .. } => {
+                    expected_messages.remove(nonce)
+                }
+            };
+            report.violations.push(match expected {
+                Some(expected) => CoinsToSpendViolation::Mismatched { key, expected },
+                None => CoinsToSpendViolation::Stale { key },
+            });
+        }
+
+        report.violations.extend(
+            expected_coins
+                .into_values()
+                .chain(expected_messages.into_values())
+                .map(|expected| CoinsToSpendViolation::Missing { expected }),
+        );
+
+        Ok(report)
+    }
+
+    /// Remove the stale and mismatched entries and insert the expected ones,
+    /// all in a single transaction
+    pub fn regenerate(&self, database_handle: &DatabaseHandle) -> anyhow::Result<()> {
+        let mut tx = database_handle.database().off_chain().read_transaction();
+
+        let removed: BTreeSet<_> = self
+            .violations
+            .iter()
+            .filter_map(|violation| match violation {
+                CoinsToSpendViolation::Stale { key }
+                | CoinsToSpendViolation::Mismatched { key, .. } => Some(key),
+                CoinsToSpendViolation::Missing { .. } => None,
+            })
+            .collect();
+        for key in removed {
+            tx.storage_as_mut::<CoinsToSpendIndex>().remove(key)?;
+        }
+
+        for violation in &self.violations {
+            match violation {
+                CoinsToSpendViolation::Mismatched { expected, .. }
+                | CoinsToSpendViolation::Missing { expected } => {
+                    tx.storage_as_mut::<CoinsToSpendIndex>()
+                        .insert(expected, &())?;
+                }
+                CoinsToSpendViolation::Stale { .. } => {}
+            }
+        }
+
+        database_handle.commit_changes(Database::OffChain, tx.into_changes())
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for violation in &self.violations {
+            println!("{violation}");
+        }
+        println!(
+            "Checked {} index entry(ies), found {} problem(s)",
+            self.checked,
+            self.violations.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_core::{
+        database::{
+            database_description::{
+                DatabaseDescription,
+                DatabaseMetadata,
+                off_chain::OffChain,
+            },
+            metadata::MetadataTable,
+        },
+        types::{
+            entities::coins::coin::CompressedCoin,
+            fuel_tx::ConsensusParameters,
+        },
+    };
+    use fuel_core_storage::tables::ConsensusParametersVersions;
+
+    use super::*;
+
+    fn config(dir: &tempfile::TempDir) -> DatabaseConfig {
+        DatabaseConfig {
+            path: dir.path().to_string_lossy().into_owned(),
+        }
+    }
+
+    fn open(dir: &tempfile::TempDir) -> DatabaseHandle {
+        DatabaseHandle::try_new_historical(
+            Database::OffChain,
+            &[Database::OnChain],
+            config(dir),
+        )
+        .unwrap()
+    }
+
+    fn index_key(output_index: u16) -> CoinsToSpendIndexKey {
+        let utxo_id = UtxoId::new(Default::default(), output_index);
+        let mut coin = CompressedCoin::default();
+        coin.set_owner(Address::from([1; 32]));
+        coin.set_amount(u64::from(output_index).saturating_add(1));
+        CoinsToSpendIndexKey::from_coin(&coin.uncompress(utxo_id))
+    }
+
+    fn stored_index(dir: &tempfile::TempDir) -> BTreeSet<CoinsToSpendIndexKey> {
+        let database_handle = open(dir);
+        let index = database_handle
+            .database()
+            .off_chain()
+            .iter_all_keys::<CoinsToSpendIndex>(None)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        database_handle.shutdown();
+        index
+    }
+
+    /// Coins 0 and 1 exist, but the index has coins 0 and 2
+    fn stale_index(dir: &tempfile::TempDir) {
+        let database_handle = open(dir);
+
+        let mut tx = database_handle.database().on_chain().read_transaction();
+        tx.storage_as_mut::<ConsensusParametersVersions>()
+            .insert(&0, &ConsensusParameters::default())
+            .unwrap();
+        for output_index in [0, 1] {
+            let mut coin = CompressedCoin::default();
+            coin.set_owner(Address::from([1; 32]));
+            coin.set_amount(u64::from(output_index).saturating_add(1));
+            tx.storage_as_mut::<Coins>()
+                .insert(&UtxoId::new(Default::default(), output_index), &coin)
+                .unwrap();
+        }
+        database_handle
+            .commit_changes(Database::OnChain, tx.into_changes())
+            .unwrap();
+
+        let mut tx = database_handle.database().off_chain().read_transaction();
+        tx.storage_as_mut::<MetadataTable<OffChain>>()
+            .insert(
+                &(),
+                &DatabaseMetadata::V2 {
+                    version: OffChain::version(),
+                    height: Default::default(),
+                    indexation_availability: [IndexationKind::CoinsToSpend].into(),
+                },
+            )
+            .unwrap();
+        for output_index in [0, 2] {
+            tx.storage_as_mut::<CoinsToSpendIndex>()
+                .insert(&index_key(output_index), &())
+                .unwrap();
+        }
+        database_handle
+            .commit_changes(Database::OffChain, tx.into_changes())
+            .unwrap();
+
+        database_handle.shutdown();
+    }
+
+    #[test]
+    fn regenerate_replaces_the_stale_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        stale_index(&dir);
+
+        VerifyCoinsToSpendArgs {
+            database_config: config(&dir),
+            owner: None,
+            regenerate: false,
+        }
+        .run()
+        .unwrap();
+        assert_eq!(stored_index(&dir), [index_key(0), index_key(2)].into());
+
+        VerifyCoinsToSpendArgs {
+            database_config: config(&dir),
+            owner: None,
+            regenerate: true,
+        }
+        .run()
+        .unwrap();
+        assert_eq!(stored_index(&dir), [index_key(0), index_key(1)].into());
+    }
+}
@@ -0,0 +1,276 @@
+//! Decompress a `CompressedBlocks` entry and compare it with the on-chain block
+
+use core::{
+    future::Future,
+    time::Duration,
+};
+
+use fuel_core::{
+    database::{
+        Database as FuelDatabase,
+        database_description::DatabaseDescription,
+    },
+    state::{
+        ColumnType,
+        KeyValueView,
+    },
+    types::{
+        blockchain::header::PartialBlockHeader,
+        fuel_tx::UniqueIdentifier,
+        fuel_types::BlockHeight,
+    },
+};
+use fuel_core_compression::decompress::decompress;
+use fuel_core_compression_service::{
+    storage::CompressedBlocks,
+    temporal_registry::{
+        CompressionStorageWrapper,
+        DecompressionContext,
+    },
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    tables::{
+        FuelBlocks,
+        Transactions,
+    },
+    transactional::{
+        AtomicView,
+        HistoricalView,
+        IntoTransaction,
+    },
+};
+
+use crate::{
+    chain::chain_id,
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::{
+        FieldChange,
+        diff_json,
+    },
+};
+
+/// Verify compressed block command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyCompressedBlockArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Height of the compressed block
+    height: u32,
+
+    /// Registry retention of the node in seconds, unlimited by default
+    #[arg(long)]
+    retention: Option<u64>,
+}
+
+impl VerifyCompressedBlockArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the compressed block verification
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::Compression,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let retention = self.retention.map_or(Duration::MAX, Duration::from_secs);
+        let result = CompressedBlockReport::compute(
+            &database_handle,
+            self.height.into(),
+            retention,
+        )
+        .map(|report| report.print());
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// Result of comparing a decompressed block with the on-chain block
+#[derive(Debug, Clone)]
+pub struct CompressedBlockReport {
+    /// height of the block
+    pub height: BlockHeight,
+    /// whether the databases were read at the height before the block, otherwise the
+    /// latest state was used
+    pub historical: bool,
+    /// number of decompressed transactions
+    pub transactions: usize,
+    /// fields of the decompressed block that differ, the on-chain value first
+    pub mismatches: Vec<FieldChange>,
+}
+
+impl CompressedBlockReport {
+    /// Decompress the `CompressedBlocks` entry at `height` and compare its header and
+    /// transactions with `FuelBlocks` and `Transactions`.
+    ///
+    /// Decompression needs the registry and the coins as they were before the block,
+    /// so both databases are read at the previous height. When that history isn't
+    /// stored, the latest state is used instead, which fails for spent coins and
+    /// resolves reused registry keys to their current values.
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        height: BlockHeight,
+        retention: Duration,
+    ) -> anyhow::Result<Self> {
+        let compression = database_handle.database().compression();
+        let on_chain = database_handle.database().on_chain();
+        let block_height = *height;
+
+        let compressed = compression
+            .storage::<CompressedBlocks>()
+            .get(&height)?
+            .ok_or_else(|| {
+                anyhow::anyhow!("No compressed block at height {block_height}")
+            })?
+            .into_owned();
+        let block = on_chain
+            .storage::<FuelBlocks>()
+            .get(&height)?
+            .ok_or_else(|| anyhow::anyhow!("No on-chain block at height {block_height}"))?
+            .into_owned();
+        let chain_id = chain_id(database_handle)?;
+
+        let (compression_view, compression_historical) =
+            view_before(compression, height)?;
+        let (on_chain_view, on_chain_historical) = view_before(on_chain, height)?;
+        let historical = compression_historical && on_chain_historical;
+
+        let mut storage_tx = compression_view.into_transaction().into_transaction();
+        let context = DecompressionContext {
+            compression_storage: CompressionStorageWrapper {
+                storage_tx: &mut storage_tx,
+            },
+            onchain_db: on_chain_view,
+        };
+        let config = fuel_core_compression::Config {
+            temporal_registry_retention: retention,
+        };
+        // Decompression only reads the databases, so the future resolves on the first poll
+        let decompression = core::pin::pin!(decompress(config, context, compressed));
+        let core::task::Poll::Ready(decompressed) = decompression.poll(
+            &mut core::task::Context::from_waker(core::task::Waker::noop()),
+        ) else {
+            anyhow::bail!(
+                "Decompression of the block at height {block_height} didn't resolve"
+            );
+        };
+        let decompressed = decompressed
+            .map_err(|error| {
+                if historical {
+                    error
+                } else {
+                    error.context(
+                        "the state before the block isn't stored, the latest state was used",
+                    )
+                }
+            })
+            .map_err(|error| {
+                error.context(format!("Failed to decompress the block at height {block_height}"))
+            })?;
+
+        let mut mismatches = diff_json(
+            &serde_json::to_value(PartialBlockHeader::from(block.header()))?,
+            &serde_json::to_value(decompressed.header)?,
+        )
+        .into_iter()
+        .map(|change| FieldChange {
+            path: format!("header.{}", change.path),
+            ..change
+        })
+        .collect::<Vec<_>>();
+
+        let on_chain_ids = block.transactions();
+        if on_chain_ids.len() != decompressed.transactions.len() {
+            mismatches.push(FieldChange {
+                path: "transactions.len".to_string(),
+                before: on_chain_ids.len().into(),
+                after: decompressed.transactions.len().into(),
+            });
+        }
+
+        for (index, (tx_id, transaction)) in on_chain_ids
+            .iter()
+            .zip(&decompressed.transactions)
+            .enumerate()
+        {
+            if transaction.id(&chain_id) == *tx_id {
+                continue;
+            }
+            let path = format!("transactions[{index}]");
+            match on_chain.storage::<Transactions>().get(tx_id)? {
+                Some(stored) => mismatches.extend(
+                    diff_json(
+                        &serde_json::to_value(&*stored)?,
+                        &serde_json::to_value(transaction)?,
+                    )
+                    .into_iter()
+                    .map(|change| FieldChange {
+                        path: format!("{path}.{}", change.path),
+                        ..change
+                    }),
+                ),
+                None => mismatches.push(FieldChange {
+                    path: format!("{path}.id"),
+                    before: serde_json::to_value(tx_id)?,
+                    after: serde_json::to_value(transaction.id(&chain_id))?,
+                }),
+            }
+        }
+
+        Ok(Self {
+            height,
+            historical,
+            transactions: decompressed.transactions.len(),
+            mismatches,
+        })
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        if !self.historical {
+            println!(
+                "The state before the block isn't stored, decompressed against the latest state"
+            );
+        }
+        for mismatch in &self.mismatches {
+            println!("{mismatch}");
+        }
+        println!(
+            "Decompressed block {} with {} transaction(s), found {} mismatch(es)",
+            *self.height,
+            self.transactions,
+            self.mismatches.len()
+        );
+    }
+}
+
+/// Read-only view of a database
+type DatabaseView<Description> = KeyValueView<ColumnType<Description>, BlockHeight>;
+
+/// View of `database` at the height before `height`, or the latest view when that
+/// history isn't stored. The flag tells whether the view is historical.
+fn view_before<Description>(
+    database: &FuelDatabase<Description>,
+    height: BlockHeight,
+) -> anyhow::Result<(DatabaseView<Description>, bool)>
+where
+    Description: DatabaseDescription<Height = BlockHeight>,
+{
+    if let Some(Ok(view)) = height.pred().map(|previous| database.view_at(&previous)) {
+        return Ok((view, true));
+    }
+
+    Ok((database.latest_view()?.into_key_value_view(), false))
+}
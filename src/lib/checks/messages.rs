@@ -0,0 +1,228 @@
+//! Verify the spent state of messages across `Messages`, `SpentMessages` and `OwnedMessageIds`
+
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::messages::{
+        OwnedMessageIds,
+        SpentMessages,
+    },
+    types::fuel_types::{
+        Address,
+        Nonce,
+    },
+};
+use fuel_core_storage::{
+    iter::IteratorOverTable,
+    tables::Messages,
+};
+
+use crate::databases::{
+    Database,
+    DatabaseConfig,
+    DatabaseHandle,
+};
+
+/// Verify messages command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyMessagesArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+}
+
+impl VerifyMessagesArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the messages verification
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let report = MessagesReport::compute(&database_handle)?;
+        report.print();
+
+        database_handle.shutdown();
+
+        Ok(())
+    }
+}
+
+/// A message whose spent state is inconsistent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageViolation {
+    /// The message is live in `Messages` and recorded in `SpentMessages`
+    LiveAndSpent {
+        /// nonce of the message
+        nonce: Nonce,
+        /// recipient of the message
+        recipient: Address,
+    },
+    /// The message is live in `Messages` but not indexed in `OwnedMessageIds`
+    NotIndexed {
+        /// nonce of the message
+        nonce: Nonce,
+        /// recipient of the message
+        recipient: Address,
+    },
+    /// The message is indexed in `OwnedMessageIds` under an owner other than its recipient
+    WrongOwner {
+        /// nonce of the message
+        nonce: Nonce,
+        /// recipient of the message
+        recipient: Address,
+        /// owner the message is indexed under
+        owner: Address,
+    },
+    /// The message is spent but still indexed in `OwnedMessageIds`
+    SpentButIndexed {
+        /// nonce of the message
+        nonce: Nonce,
+        /// owner the message is indexed under
+        owner: Address,
+    },
+    /// The message is indexed in `OwnedMessageIds`, but is neither live nor spent
+    Unknown {
+        /// nonce of the message
+        nonce: Nonce,
+        /// owner the message is indexed under
+        owner: Address,
+    },
+}
+
+impl core::fmt::Display for MessageViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::LiveAndSpent { nonce, recipient } => write!(
+                f,
+                "message is both live and spent: nonce={nonce} recipient={recipient}"
+            ),
+            Self::NotIndexed { nonce, recipient } => write!(
+                f,
+                "live message is not indexed: nonce={nonce} recipient={recipient}"
+            ),
+            Self::WrongOwner {
+                nonce,
+                recipient,
+                owner,
+            } => write!(
+                f,
+                "message is indexed under the wrong owner: nonce={nonce} recipient={recipient} owner={owner}"
+            ),
+            Self::SpentButIndexed { nonce, owner } => write!(
+                f,
+                "spent message is still indexed: nonce={nonce} owner={owner}"
+            ),
+            Self::Unknown { nonce, owner } => write!(
+                f,
+                "indexed message is neither live nor spent: nonce={nonce} owner={owner}"
+            ),
+        }
+    }
+}
+
+/// Result of cross-referencing the message tables
+#[derive(Debug, Clone, Default)]
+pub struct MessagesReport {
+    /// number of live messages
+    pub live: usize,
+    /// number of spent messages
+    pub spent: usize,
+    /// all inconsistent messages
+    pub violations: Vec<MessageViolation>,
+}
+
+impl MessagesReport {
+    /// Cross-reference the on-chain `Messages` with the off-chain
+    /// `SpentMessages` and `OwnedMessageIds`
+    pub fn compute(database_handle: &DatabaseHandle) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let mut live = BTreeMap::<Nonce, Address>::new();
+        for entry in on_chain.iter_all::<Messages>(None) {
+            let (nonce, message) = entry?;
+            live.insert(nonce, *message.recipient());
+        }
+
+        let mut spent = BTreeSet::<Nonce>::new();
+        for entry in off_chain.iter_all_keys::<SpentMessages>(None) {
+            spent.insert(entry?);
+        }
+
+        let mut indexed = BTreeMap::<Nonce, Vec<Address>>::new();
+        for entry in off_chain.iter_all_keys::<OwnedMessageIds>(None) {
+            let key = entry?;
+            indexed
+                .entry(*key.nonce())
+                .or_default()
+                .push(*key.address());
+        }
+
+        let mut report = Self {
+            live: live.len(),
+            spent: spent.len(),
+            ..Default::default()
+        };
+
+        for (nonce, recipient) in &live {
+            let (nonce, recipient) = (*nonce, *recipient);
+            if spent.contains(&nonce) {
+                report
+                    .violations
+                    .push(MessageViolation::LiveAndSpent { nonce, recipient });
+            }
+
+            let owners = indexed.remove(&nonce).unwrap_or_default();
+            if !owners.contains(&recipient) {
+                report
+                    .violations
+                    .push(MessageViolation::NotIndexed { nonce, recipient });
+            }
+            report.violations.extend(
+                owners
+                    .into_iter()
+                    .filter(|owner| *owner != recipient)
+                    .map(|owner| MessageViolation::WrongOwner {
+                        nonce,
+                        recipient,
+                        owner,
+                    }),
+            );
+        }
+
+        for (nonce, owners) in indexed {
+            report.violations.extend(owners.into_iter().map(|owner| {
+                if spent.contains(&nonce) {
+                    MessageViolation::SpentButIndexed { nonce, owner }
+                } else {
+                    MessageViolation::Unknown { nonce, owner }
+                }
+            }));
+        }
+
+        Ok(report)
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for violation in &self.violations {
+            println!("{violation}");
+        }
+        println!(
+            "Checked {} live and {} spent message(s), found {} problem(s)",
+            self.live,
+            self.spent,
+            self.violations.len()
+        );
+    }
+}
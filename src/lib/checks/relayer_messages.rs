@@ -0,0 +1,198 @@
+//! Reconcile the relayer message events with the on-chain `Messages` and the
+//! off-chain `SpentMessages`
+
+use std::collections::BTreeMap;
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::messages::SpentMessages,
+    types::{
+        blockchain::primitives::DaBlockHeight,
+        entities::relayer::message::Message,
+        fuel_types::Nonce,
+        services::relayer::Event,
+    },
+};
+use fuel_core_relayer::storage::EventsHistory;
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::Messages,
+};
+
+use crate::{
+    chain::latest_block_da_height,
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+};
+
+/// Verify relayer messages command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyRelayerMessagesArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+}
+
+impl VerifyRelayerMessagesArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the relayer messages verification
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::Relayer,
+            &[Database::OnChain, Database::OffChain],
+            self.database_config.clone(),
+        )?;
+
+        let result =
+            RelayerMessagesReport::compute(&database_handle).map(|report| report.print());
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// A message that differs between the relayer history and the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelayerMessageViolation {
+    /// The relayed message is neither live nor spent
+    Missing {
+        /// nonce of the message
+        nonce: Nonce,
+        /// DA height the message was relayed at
+        da_height: DaBlockHeight,
+    },
+    /// The live message differs from the relayed one
+    Mismatched {
+        /// nonce of the message
+        nonce: Nonce,
+        /// DA height the message was relayed at
+        da_height: DaBlockHeight,
+    },
+    /// The live message has no relayer event
+    NotRelayed {
+        /// nonce of the message
+        nonce: Nonce,
+        /// DA height stored in the message
+        da_height: DaBlockHeight,
+    },
+}
+
+impl core::fmt::Display for RelayerMessageViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Missing { nonce, da_height } => write!(
+                f,
+                "relayed message is neither live nor spent: nonce={nonce} da_height={da_height}"
+            ),
+            Self::Mismatched { nonce, da_height } => write!(
+                f,
+                "live message differs from the relayed one: nonce={nonce} da_height={da_height}"
+            ),
+            Self::NotRelayed { nonce, da_height } => write!(
+                f,
+                "live message has no relayer event: nonce={nonce} da_height={da_height}"
+            ),
+        }
+    }
+}
+
+/// Result of reconciling the relayer messages with the chain
+#[derive(Debug, Clone, Default)]
+pub struct RelayerMessagesReport {
+    /// highest DA height referenced by the stored blocks
+    pub da_height: DaBlockHeight,
+    /// number of relayed messages up to `da_height`
+    pub relayed: usize,
+    /// number of live messages
+    pub live: usize,
+    /// all discrepancies found
+    pub violations: Vec<RelayerMessageViolation>,
+}
+
+impl RelayerMessagesReport {
+    /// Check that every message relayed up to the DA height of the latest block is
+    /// live in `Messages` or recorded in `SpentMessages`, and that every live message
+    /// was relayed.
+    ///
+    /// Messages imported from the chain config at genesis have no relayer event, so
+    /// they are reported as not relayed too.
+    pub fn compute(database_handle: &DatabaseHandle) -> anyhow::Result<Self> {
+        let relayer = database_handle.database().relayer();
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let da_height = latest_block_da_height(database_handle)?;
+
+        let mut relayed = BTreeMap::<Nonce, Message>::new();
+        for entry in relayer.iter_all::<EventsHistory>(None) {
+            let (_, events) = entry?;
+            relayed.extend(events.into_iter().filter_map(|event| match event {
+                Event::Message(message) => Some((*message.nonce(), message)),
+                Event::Transaction(_) => None,
+            }));
+        }
+
+        let mut report = Self {
+            da_height,
+            ..Default::default()
+        };
+
+        for (nonce, message) in &relayed {
+            if message.da_height() > da_height {
+                continue;
+            }
+            report.relayed = report.relayed.saturating_add(1);
+
+            let live = on_chain.storage::<Messages>().get(nonce)?;
+            match live {
+                Some(live) if *live != *message => {
+                    report.violations.push(RelayerMessageViolation::Mismatched {
+                        nonce: *nonce,
+                        da_height: message.da_height(),
+                    })
+                }
+                Some(_) => {}
+                None if off_chain.storage::<SpentMessages>().contains_key(nonce)? => {}
+                None => report.violations.push(RelayerMessageViolation::Missing {
+                    nonce: *nonce,
+                    da_height: message.da_height(),
+                }),
+            }
+        }
+
+        for entry in on_chain.iter_all::<Messages>(None) {
+            let (nonce, message) = entry?;
+            report.live = report.live.saturating_add(1);
+            if !relayed.contains_key(&nonce) {
+                report.violations.push(RelayerMessageViolation::NotRelayed {
+                    nonce,
+                    da_height: message.da_height(),
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for violation in &self.violations {
+            println!("{violation}");
+        }
+        println!(
+            "Checked {} relayed message(s) up to DA height {} and {} live message(s), found {} problem(s)",
+            self.relayed,
+            self.da_height,
+            self.live,
+            self.violations.len()
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Decode the off-chain `Statistic` counters and recount them from the stored blocks
+
+use fuel_core::fuel_core_graphql_api::storage::statistic::StatisticTable;
+use fuel_core_storage::{
+    StorageAsMut,
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::FuelBlocks,
+    transactional::ReadTransaction,
+};
+
+use crate::databases::{
+    Database,
+    DatabaseConfig,
+    DatabaseHandle,
+};
+
+/// Key of the total number of transactions, as written by the off-chain worker
+pub const TX_COUNT: &str = "total_tx_count";
+
+/// Statistic command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct StatisticArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Recount the counters from the on-chain `FuelBlocks` and report the mismatches
+    #[arg(long)]
+    recount: bool,
+
+    /// Write the recounted values of the mismatched counters
+    #[arg(long, requires = "recount")]
+    repair: bool,
+}
+
+impl StatisticArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the statistic command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let result = self.statistic(&database_handle);
+        database_handle.shutdown();
+
+        result
+    }
+
+    fn statistic(&self, database_handle: &DatabaseHandle) -> anyhow::Result<()> {
+        let entries = StatisticEntry::list(database_handle)?;
+        for entry in &entries {
+            println!("{entry}");
+        }
+        println!("Found {} statistic key(s)", entries.len());
+
+        if !self.recount {
+            return Ok(());
+        }
+
+        let report = StatisticReport::compute(database_handle)?;
+        report.print();
+
+        if self.repair && !report.mismatches.is_empty() {
+            report.repair(database_handle)?;
+            println!("Repaired {} counter(s)", report.mismatches.len());
+        }
+
+        Ok(())
+    }
+}
+
+/// A decoded `Statistic` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatisticEntry {
+    /// key of the statistic
+    pub key: String,
+    /// the value decoded as a counter, or the reason it could not be decoded
+    pub value: Result<u64, String>,
+}
+
+impl core::fmt::Display for StatisticEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.value {
+            Ok(value) => write!(f, "{}: {value}", self.key),
+            Err(error) => write!(f, "{}: undecodable ({error})", self.key),
+        }
+    }
+}
+
+impl StatisticEntry {
+    /// Decode every key of the `Statistic` column, reading the values as counters
+    pub fn list(database_handle: &DatabaseHandle) -> anyhow::Result<Vec<Self>> {
+        let off_chain = database_handle.database().off_chain();
+
+        off_chain
+            .iter_all_keys::<StatisticTable<u64>>(None)
+            .map(|key| {
+                let key = key?;
+                let value = match off_chain.storage::<StatisticTable<u64>>().get(&key) {
+                    Ok(value) => value
+                        .map(|value| value.into_owned())
+                        .ok_or_else(|| "removed while reading".to_string()),
+                    Err(error) => Err(error.to_string()),
+                };
+                Ok(Self { key, value })
+            })
+            .collect()
+    }
+}
+
+/// A counter whose stored value differs from the recounted one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatisticMismatch {
+    /// key of the counter
+    pub key: &'static str,
+    /// stored value, if any
+    pub stored: Option<u64>,
+    /// value recounted from the stored blocks
+    pub expected: u64,
+}
+
+impl core::fmt::Display for StatisticMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.stored {
+            Some(stored) => write!(
+                f,
+                "mismatched counter: key={} stored={stored} expected={}",
+                self.key, self.expected
+            ),
+            None => write!(
+                f,
+                "missing counter: key={} expected={}",
+                self.key, self.expected
+            ),
+        }
+    }
+}
+
+/// Result of recounting the `Statistic` counters from the stored blocks
+#[derive(Debug, Clone, Default)]
+pub struct StatisticReport {
+    /// number of blocks in `FuelBlocks`
+    pub blocks: usize,
+    /// all counters that don't match
+    pub mismatches: Vec<StatisticMismatch>,
+}
+
+impl StatisticReport {
+    /// Recount the transactions of every block in `FuelBlocks` and compare the total
+    /// with the stored counter
+    pub fn compute(database_handle: &DatabaseHandle) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let mut blocks = 0usize;
+        let mut tx_count = 0u64;
+        for entry in on_chain.iter_all::<FuelBlocks>(None) {
+            let (_, block) = entry?;
+            tx_count = tx_count.saturating_add(block.transactions().len() as u64);
+            blocks = blocks.saturating_add(1);
+        }
+
+        let stored = off_chain
+            .storage::<StatisticTable<u64>>()
+            .get(TX_COUNT)?
+            .map(|value| value.into_owned());
+
+        let mut mismatches = vec![];
+        if stored != Some(tx_count) {
+            mismatches.push(StatisticMismatch {
+                key: TX_COUNT,
+                stored,
+                expected: tx_count,
+            });
+        }
+
+        Ok(Self { blocks, mismatches })
+    }
+
+    /// Overwrite the mismatched counters with the recounted values
+    pub fn repair(&self, database_handle: &DatabaseHandle) -> anyhow::Result<()> {
+        let mut tx = database_handle.database().off_chain().read_transaction();
+
+        for mismatch in &self.mismatches {
+            tx.storage_as_mut::<StatisticTable<u64>>()
+                .insert(mismatch.key, &mismatch.expected)?;
+        }
+
+        database_handle.commit_changes(Database::OffChain, tx.into_changes())
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for mismatch in &self.mismatches {
+            println!("{mismatch}");
+        }
+        println!(
+            "Recounted {} block(s), found {} mismatched counter(s)",
+            self.blocks,
+            self.mismatches.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_core::types::blockchain::block::CompressedBlock;
+
+    use super::*;
+
+    fn config(dir: &tempfile::TempDir) -> DatabaseConfig {
+        DatabaseConfig {
+            path: dir.path().to_string_lossy().into_owned(),
+        }
+    }
+
+    fn open(dir: &tempfile::TempDir) -> DatabaseHandle {
+        DatabaseHandle::try_new_historical(
+            Database::OffChain,
+            &[Database::OnChain],
+            config(dir),
+        )
+        .unwrap()
+    }
+
+    fn tx_count(dir: &tempfile::TempDir) -> Option<u64> {
+        let database_handle = open(dir);
+        let tx_count = database_handle
+            .database()
+            .off_chain()
+            .storage::<StatisticTable<u64>>()
+            .get(TX_COUNT)
+            .unwrap()
+            .map(|value| value.into_owned());
+        database_handle.shutdown();
+        tx_count
+    }
+
+    /// Blocks with 2 and 1 transactions, while the counter says 5
+    fn wrong_counter(dir: &tempfile::TempDir) {
+        let database_handle = open(dir);
+
+        let mut tx = database_handle.database().on_chain().read_transaction();
+        for (height, transactions) in [(1u32, 2), (2, 1)] {
+            let mut block = CompressedBlock::default();
+            block.header_mut().set_block_height(height.into());
+            *block.transactions_mut() = vec![Default::default(); transactions];
+            tx.storage_as_mut::<FuelBlocks>()
+                .insert(&height.into(), &block)
+                .unwrap();
+        }
+        database_handle
+            .commit_changes(Database::OnChain, tx.into_changes())
+            .unwrap();
+
+        let mut tx = database_handle.database().off_chain().read_transaction();
+        tx.storage_as_mut::<StatisticTable<u64>>()
+            .insert(TX_COUNT, &5)
+            .unwrap();
+        database_handle
+            .commit_changes(Database::OffChain, tx.into_changes())
+            .unwrap();
+
+        database_handle.shutdown();
+    }
+
+    #[test]
+    fn repair_writes_the_recounted_counter() {
+        let dir = tempfile::tempdir().unwrap();
+        wrong_counter(&dir);
+
+        StatisticArgs {
+            database_config: config(&dir),
+            recount: true,
+            repair: false,
+        }
+        .run()
+        .unwrap();
+        assert_eq!(tx_count(&dir), Some(5));
+
+        StatisticArgs {
+            database_config: config(&dir),
+            recount: true,
+            repair: true,
+        }
+        .run()
+        .unwrap();
+        assert_eq!(tx_count(&dir), Some(3));
+    }
+}
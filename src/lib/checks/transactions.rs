@@ -0,0 +1,236 @@
+//! Verify `ProcessedTransactions` and `TransactionStatuses` against the stored blocks
+
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::{
+        old::{
+            OldFuelBlocks,
+            OldTransactions,
+        },
+        transactions::TransactionStatuses,
+    },
+    types::{
+        fuel_tx::TxId,
+        fuel_types::BlockHeight,
+        services::transaction_status::TransactionExecutionStatus,
+    },
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::{
+        FuelBlocks,
+        ProcessedTransactions,
+    },
+};
+
+use crate::databases::{
+    Database,
+    DatabaseConfig,
+    DatabaseHandle,
+};
+
+/// Verify transactions command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyTransactionsArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+}
+
+impl VerifyTransactionsArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the transactions verification
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let report = TransactionsReport::compute(&database_handle)?;
+        report.print();
+
+        database_handle.shutdown();
+
+        Ok(())
+    }
+}
+
+/// A transaction whose indexing is inconsistent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionViolation {
+    /// The processed transaction has no `TransactionStatuses` entry
+    MissingStatus {
+        /// id of the transaction
+        tx_id: TxId,
+    },
+    /// The processed transaction is neither in a block nor in `OldTransactions`
+    NotInBlock {
+        /// id of the transaction
+        tx_id: TxId,
+    },
+    /// The status points at a block height that isn't stored
+    UnknownBlock {
+        /// id of the transaction
+        tx_id: TxId,
+        /// height stored in the status
+        height: BlockHeight,
+    },
+    /// The status points at a block that doesn't include the transaction
+    WrongBlock {
+        /// id of the transaction
+        tx_id: TxId,
+        /// height stored in the status
+        stored: BlockHeight,
+        /// height of the block including the transaction, if any
+        expected: Option<BlockHeight>,
+    },
+}
+
+impl core::fmt::Display for TransactionViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingStatus { tx_id } => {
+                write!(f, "processed transaction has no status: tx_id={tx_id}")
+            }
+            Self::NotInBlock { tx_id } => write!(
+                f,
+                "processed transaction is not in any block: tx_id={tx_id}"
+            ),
+            Self::UnknownBlock { tx_id, height } => write!(
+                f,
+                "status points at an unknown block: tx_id={tx_id} height={}",
+                **height
+            ),
+            Self::WrongBlock {
+                tx_id,
+                stored,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "status points at the wrong block: tx_id={tx_id} stored={}",
+                    **stored
+                )?;
+                match expected {
+                    Some(expected) => write!(f, " expected={}", **expected),
+                    None => write!(f, " expected=none"),
+                }
+            }
+        }
+    }
+}
+
+/// Result of cross-referencing the transaction tables with the blocks
+#[derive(Debug, Clone, Default)]
+pub struct TransactionsReport {
+    /// number of processed transactions
+    pub processed: usize,
+    /// number of transaction statuses
+    pub statuses: usize,
+    /// all inconsistent transactions
+    pub violations: Vec<TransactionViolation>,
+}
+
+impl TransactionsReport {
+    /// Cross-reference the on-chain `ProcessedTransactions` with the off-chain
+    /// `TransactionStatuses`, and both with the transactions included in `FuelBlocks`,
+    /// `OldFuelBlocks` and `OldTransactions`
+    pub fn compute(database_handle: &DatabaseHandle) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let mut heights = BTreeSet::<BlockHeight>::new();
+        let mut included = BTreeMap::<TxId, BlockHeight>::new();
+        for entry in on_chain.iter_all::<FuelBlocks>(None) {
+            let (height, block) = entry?;
+            heights.insert(height);
+            included.extend(block.transactions().iter().map(|tx_id| (*tx_id, height)));
+        }
+        for entry in off_chain.iter_all::<OldFuelBlocks>(None) {
+            let (height, block) = entry?;
+            heights.insert(height);
+            included.extend(block.transactions().iter().map(|tx_id| (*tx_id, height)));
+        }
+
+        let mut report = Self::default();
+
+        for entry in on_chain.iter_all_keys::<ProcessedTransactions>(None) {
+            let tx_id = entry?;
+            report.processed = report.processed.saturating_add(1);
+
+            if !off_chain
+                .storage::<TransactionStatuses>()
+                .contains_key(&tx_id)?
+            {
+                report
+                    .violations
+                    .push(TransactionViolation::MissingStatus { tx_id });
+            }
+
+            if !included.contains_key(&tx_id)
+                && !off_chain
+                    .storage::<OldTransactions>()
+                    .contains_key(&tx_id)?
+            {
+                report
+                    .violations
+                    .push(TransactionViolation::NotInBlock { tx_id });
+            }
+        }
+
+        for entry in off_chain.iter_all::<TransactionStatuses>(None) {
+            let (tx_id, status) = entry?;
+            report.statuses = report.statuses.saturating_add(1);
+
+            let height = match status {
+                TransactionExecutionStatus::Success { block_height, .. }
+                | TransactionExecutionStatus::Failed { block_height, .. } => block_height,
+                TransactionExecutionStatus::Submitted { .. }
+                | TransactionExecutionStatus::SqueezedOut { .. } => continue,
+            };
+
+            let expected = included.get(&tx_id).copied();
+            if !heights.contains(&height) {
+                report
+                    .violations
+                    .push(TransactionViolation::UnknownBlock { tx_id, height });
+            } else if expected != Some(height)
+                && (expected.is_some()
+                    || !off_chain
+                        .storage::<OldTransactions>()
+                        .contains_key(&tx_id)?)
+            {
+                report.violations.push(TransactionViolation::WrongBlock {
+                    tx_id,
+                    stored: height,
+                    expected,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for violation in &self.violations {
+            println!("{violation}");
+        }
+        println!(
+            "Checked {} processed transaction(s) and {} status(es), found {} problem(s)",
+            self.processed,
+            self.statuses,
+            self.violations.len()
+        );
+    }
+}
@@ -40,6 +40,11 @@ pub struct CmdConfig {
     /// Value to write
     #[arg(long, short)]
     value: Option<String>,
+
+    /// Read the value as of this height instead of the latest one, a DA height for
+    /// the relayer database
+    #[arg(long)]
+    at_height: Option<u64>,
 }
 
 #[derive(clap::Subcommand, Debug, Clone)]
@@ -49,14 +54,113 @@ enum Command {
     Inspect(CmdConfig),
     /// Mutate database k-v pairs
     Mutate(CmdConfig),
+    /// Recompute coin and message balances and verify the stored aggregates
+    VerifyBalances(crate::checks::balances::VerifyBalancesArgs),
+    /// Verify the coins to spend index against the on-chain coins and messages
+    VerifyCoinsToSpend(crate::checks::coins_to_spend::VerifyCoinsToSpendArgs),
+    /// Verify the spent state of messages across the on-chain and off-chain databases
+    VerifyMessages(crate::checks::messages::VerifyMessagesArgs),
+    /// Verify the block id to height index against the stored blocks
+    VerifyBlockIds(crate::checks::block_ids::VerifyBlockIdsArgs),
+    /// Verify processed transactions and their statuses against the stored blocks
+    VerifyTransactions(crate::checks::transactions::VerifyTransactionsArgs),
+    /// Look up a transaction by id across all databases
+    Tx(crate::queries::transaction::TransactionArgs),
+    /// Look up a block by height or id
+    Block(crate::queries::block::BlockArgs),
+    /// Report the coins, messages, balances and transactions of an owner
+    Owner(crate::queries::owner::OwnerArgs),
+    /// Report the info, code, assets and storage of a contract
+    Contract(crate::queries::contract::ContractArgs),
+    /// Extract contract bytecode, a blob or uploaded bytecode to a file
+    Extract(crate::queries::extract::ExtractArgs),
+    /// List, decode and diff the consensus parameters versions
+    ConsensusParameters(crate::queries::consensus_parameters::ConsensusParametersArgs),
+    /// List the state transition bytecode versions and the state of their uploads
+    StateTransitionBytecodes(
+        crate::queries::state_transition_bytecodes::StateTransitionBytecodesArgs,
+    ),
+    /// Inspect and reset the per-table progress of a genesis import
+    Genesis(crate::queries::genesis::GenesisArgs),
+    /// Decode the statistic counters and recount them from the stored blocks
+    Statistic(crate::checks::statistic::StatisticArgs),
+    /// List the assets indexed in `AssetsInfo`
+    Assets(crate::queries::assets::AssetsArgs),
+    /// Verify the asset supplies by replaying the mint and burn receipts
+    VerifyAssets(crate::checks::assets::VerifyAssetsArgs),
+    /// Decode the gas price service state and replay the gas price algorithm
+    GasPrice(crate::queries::gas_price::GasPriceArgs),
+    /// Decode the relayer events history per DA height
+    Relayer(crate::queries::relayer::RelayerArgs),
+    /// Reconcile the relayer message events with the on-chain and off-chain messages
+    VerifyRelayerMessages(crate::checks::relayer_messages::VerifyRelayerMessagesArgs),
+    /// Track the forced transactions through their relayed status and inclusion
+    RelayedTransactions(crate::queries::relayed_transactions::RelayedTransactionsArgs),
+    /// Resolve compression registry keys and values and verify the registry index
+    Registry(crate::queries::registry::RegistryArgs),
+    /// Decompress a compressed block and compare it with the on-chain block
+    VerifyCompressedBlock(crate::checks::compressed_blocks::VerifyCompressedBlockArgs),
+    /// Decode the compression evictor state and simulate the next evictions
+    Evictor(crate::queries::evictor::EvictorArgs),
+    /// Roll a database back to an earlier block height
+    Rollback(crate::rollback::RollbackArgs),
 }
 
-impl Command {
-    fn into_cmd_config(self) -> CmdConfig {
-        match self {
-            Self::Mutate(config) | Self::Inspect(config) => config,
-        }
-    }
+/// Validated command
+#[derive(Debug, Clone)]
+pub enum ValidatedCommand {
+    /// Inspect or mutate database k-v pairs
+    KeyValue(ValidatedFuelCoreInspectorCliArgs),
+    /// Recompute coin and message balances and verify the stored aggregates
+    VerifyBalances(crate::checks::balances::VerifyBalancesArgs),
+    /// Verify the coins to spend index against the on-chain coins and messages
+    VerifyCoinsToSpend(crate::checks::coins_to_spend::VerifyCoinsToSpendArgs),
+    /// Verify the spent state of messages across the on-chain and off-chain databases
+    VerifyMessages(crate::checks::messages::VerifyMessagesArgs),
+    /// Verify the block id to height index against the stored blocks
+    VerifyBlockIds(crate::checks::block_ids::VerifyBlockIdsArgs),
+    /// Verify processed transactions and their statuses against the stored blocks
+    VerifyTransactions(crate::checks::transactions::VerifyTransactionsArgs),
+    /// Look up a transaction by id across all databases
+    Tx(crate::queries::transaction::TransactionArgs),
+    /// Look up a block by height or id
+    Block(crate::queries::block::BlockArgs),
+    /// Report the coins, messages, balances and transactions of an owner
+    Owner(crate::queries::owner::OwnerArgs),
+    /// Report the info, code, assets and storage of a contract
+    Contract(crate::queries::contract::ContractArgs),
+    /// Extract contract bytecode, a blob or uploaded bytecode to a file
+    Extract(crate::queries::extract::ExtractArgs),
+    /// List, decode and diff the consensus parameters versions
+    ConsensusParameters(crate::queries::consensus_parameters::ConsensusParametersArgs),
+    /// List the state transition bytecode versions and the state of their uploads
+    StateTransitionBytecodes(
+        crate::queries::state_transition_bytecodes::StateTransitionBytecodesArgs,
+    ),
+    /// Inspect and reset the per-table progress of a genesis import
+    Genesis(crate::queries::genesis::GenesisArgs),
+    /// Decode the statistic counters and recount them from the stored blocks
+    Statistic(crate::checks::statistic::StatisticArgs),
+    /// List the assets indexed in `AssetsInfo`
+    Assets(crate::queries::assets::AssetsArgs),
+    /// Verify the asset supplies by replaying the mint and burn receipts
+    VerifyAssets(crate::checks::assets::VerifyAssetsArgs),
+    /// Decode the gas price service state and replay the gas price algorithm
+    GasPrice(crate::queries::gas_price::GasPriceArgs),
+    /// Decode the relayer events history per DA height
+    Relayer(crate::queries::relayer::RelayerArgs),
+    /// Reconcile the relayer message events with the on-chain and off-chain messages
+    VerifyRelayerMessages(crate::checks::relayer_messages::VerifyRelayerMessagesArgs),
+    /// Track the forced transactions through their relayed status and inclusion
+    RelayedTransactions(crate::queries::relayed_transactions::RelayedTransactionsArgs),
+    /// Resolve compression registry keys and values and verify the registry index
+    Registry(crate::queries::registry::RegistryArgs),
+    /// Decompress a compressed block and compare it with the on-chain block
+    VerifyCompressedBlock(crate::checks::compressed_blocks::VerifyCompressedBlockArgs),
+    /// Decode the compression evictor state and simulate the next evictions
+    Evictor(crate::queries::evictor::EvictorArgs),
+    /// Roll a database back to an earlier block height
+    Rollback(crate::rollback::RollbackArgs),
 }
 
 /// Validated FuelCoreInspectorCliArgs
@@ -72,6 +176,8 @@ pub struct ValidatedFuelCoreInspectorCliArgs {
     key: std::sync::Arc<[u8]>,
     /// value
     value: std::sync::Arc<[u8]>,
+    /// height to read at
+    at_height: Option<u64>,
     /// command
     cmd: CommandWithoutConfig,
 }
@@ -101,6 +207,11 @@ impl ValidatedFuelCoreInspectorCliArgs {
         &self.value
     }
 
+    /// height to read at, the latest state if `None`
+    pub fn at_height(&self) -> Option<u64> {
+        self.at_height
+    }
+
     /// database
     pub fn database(&self) -> &crate::databases::Database {
         &self.database
@@ -117,7 +228,9 @@ impl ValidatedFuelCoreInspectorCliArgs {
     }
 }
 
-fn hex_string_to_bytes<S>(hex_string: S) -> anyhow::Result<std::sync::Arc<[u8]>>
+pub(crate) fn hex_string_to_bytes<S>(
+    hex_string: S,
+) -> anyhow::Result<std::sync::Arc<[u8]>>
 where
     S: AsRef<str>,
 {
@@ -140,36 +253,158 @@ where
     Ok(std::sync::Arc::from(bytes))
 }
 
+fn validate_database_path(
+    database_config: &crate::databases::DatabaseConfig,
+) -> anyhow::Result<()> {
+    if !std::path::Path::new(&database_config.path).exists() {
+        return Err(anyhow::anyhow!(
+            "Database path `{}` does not exist",
+            database_config.path
+        ));
+    }
+
+    Ok(())
+}
+
 impl FuelCoreInspectorCliArgs {
+    /// validate and parse the command arguments
+    pub fn validate(self) -> anyhow::Result<ValidatedCommand> {
+        match self.command {
+            Command::Inspect(config) => Ok(ValidatedCommand::KeyValue(
+                Self::validate_key_value(CommandWithoutConfig::Inspect, config)?,
+            )),
+            Command::Mutate(config) => Ok(ValidatedCommand::KeyValue(
+                Self::validate_key_value(CommandWithoutConfig::Mutate, config)?,
+            )),
+            Command::VerifyBalances(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::VerifyBalances(args))
+            }
+            Command::VerifyCoinsToSpend(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::VerifyCoinsToSpend(args))
+            }
+            Command::VerifyMessages(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::VerifyMessages(args))
+            }
+            Command::VerifyBlockIds(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::VerifyBlockIds(args))
+            }
+            Command::VerifyTransactions(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::VerifyTransactions(args))
+            }
+            Command::Tx(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Tx(args))
+            }
+            Command::Block(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Block(args))
+            }
+            Command::Owner(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Owner(args))
+            }
+            Command::Contract(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Contract(args))
+            }
+            Command::Extract(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Extract(args))
+            }
+            Command::ConsensusParameters(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::ConsensusParameters(args))
+            }
+            Command::StateTransitionBytecodes(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::StateTransitionBytecodes(args))
+            }
+            Command::Genesis(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Genesis(args))
+            }
+            Command::Statistic(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Statistic(args))
+            }
+            Command::Assets(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Assets(args))
+            }
+            Command::VerifyAssets(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::VerifyAssets(args))
+            }
+            Command::GasPrice(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::GasPrice(args))
+            }
+            Command::Relayer(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Relayer(args))
+            }
+            Command::VerifyRelayerMessages(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::VerifyRelayerMessages(args))
+            }
+            Command::RelayedTransactions(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::RelayedTransactions(args))
+            }
+            Command::Registry(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Registry(args))
+            }
+            Command::VerifyCompressedBlock(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::VerifyCompressedBlock(args))
+            }
+            Command::Evictor(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Evictor(args))
+            }
+            Command::Rollback(args) => {
+                validate_database_path(args.database_config())?;
+                Ok(ValidatedCommand::Rollback(args))
+            }
+        }
+    }
+
     /// validate and parse the column into an enum variant
-    pub fn validate(self) -> anyhow::Result<ValidatedFuelCoreInspectorCliArgs> {
-        let cmd = match self.command {
-            Command::Inspect(_) => CommandWithoutConfig::Inspect,
-            Command::Mutate(_) => CommandWithoutConfig::Mutate,
-        };
+    fn validate_key_value(
+        cmd: CommandWithoutConfig,
+        config: CmdConfig,
+    ) -> anyhow::Result<ValidatedFuelCoreInspectorCliArgs> {
         let CmdConfig {
             database,
             column,
             key,
             database_config,
             value,
-        } = self.command.into_cmd_config();
+            at_height,
+        } = config;
 
         if matches!(cmd, CommandWithoutConfig::Mutate) && value.is_none() {
             return Err(anyhow::anyhow!("Value is required for mutate command"));
         }
 
+        if matches!(cmd, CommandWithoutConfig::Mutate) && at_height.is_some() {
+            return Err(anyhow::anyhow!(
+                "At height is not supported for mutate command"
+            ));
+        }
+
         let key = hex_string_to_bytes(&key)?;
         let value = value.map(hex_string_to_bytes).transpose()?;
 
         let column = database.parse_column_for_database(column.as_str())?;
 
-        if !std::path::Path::new(&database_config.path).exists() {
-            return Err(anyhow::anyhow!(
-                "Database path `{}` does not exist",
-                database_config.path
-            ));
-        }
+        validate_database_path(&database_config)?;
 
         Ok(ValidatedFuelCoreInspectorCliArgs {
             database,
@@ -178,6 +413,7 @@ impl FuelCoreInspectorCliArgs {
             database_config,
             cmd,
             value: value.unwrap_or_default(),
+            at_height,
         })
     }
 }
@@ -1,24 +1,64 @@
 //! Databases definitions
 
 use std::{
-    path::PathBuf,
+    collections::BTreeMap,
+    path::{
+        Path,
+        PathBuf,
+    },
     str::FromStr,
+    sync::Arc,
 };
 
+use anyhow::Context;
 use fuel_core::{
     combined_database::CombinedDatabase,
+    database::{
+        database_description::{
+            DatabaseDescription,
+            compression::CompressionDatabase,
+            gas_price::GasPriceDatabase,
+            off_chain::OffChain,
+            on_chain::OnChain,
+            relayer::Relayer,
+        },
+        metadata::MetadataTable,
+    },
     state::{
-        historical_rocksdb::StateRewindPolicy,
+        TransactableStorage,
+        data_source::DataSourceType,
+        historical_rocksdb::{
+            HistoricalRocksDB,
+            StateRewindPolicy,
+            description::Historical,
+            modifications_history::{
+                ModificationsHistoryV1,
+                ModificationsHistoryV2,
+            },
+        },
         rocks_db::ColumnsPolicy,
     },
+    types::{
+        blockchain::primitives::DaBlockHeight,
+        fuel_types::BlockHeight,
+    },
 };
 use fuel_core_storage::{
+    Error as StorageError,
+    iter::{
+        IterDirection,
+        IteratorOverTable,
+    },
     kv_store::{
         KeyValueInspect,
         KeyValueMutate,
         Value,
     },
-    transactional::WriteTransaction,
+    transactional::{
+        Changes,
+        HistoricalView,
+        WriteTransaction,
+    },
 };
 
 use crate::columns::Column;
@@ -65,16 +105,167 @@ pub struct DatabaseHandle {
     config: DatabaseConfig,
     /// database
     database: CombinedDatabase,
+    /// raw data sources of the opened databases
+    sources: DataSources,
+}
+
+/// Raw data sources of the opened databases, used to commit changes
+/// without going through the height checks of [`CombinedDatabase`]
+struct DataSources {
+    on_chain: Option<DataSource<OnChain>>,
+    off_chain: Option<DataSource<OffChain>>,
+    relayer: Option<DataSource<Relayer>>,
+    gas_price: Option<DataSource<GasPriceDatabase>>,
+    compression: Option<DataSource<CompressionDatabase>>,
+}
+
+/// Raw data source of a database, which also gives access to its modifications history
+type DataSource<Description> = Arc<HistoricalRocksDB<Description>>;
+
+/// Opens the database at `path` if `enabled`, otherwise falls back to a temporary one
+fn open_database_if<Description>(
+    enabled: bool,
+    path: &Path,
+    state_rewind_policy: StateRewindPolicy,
+    db_config: fuel_core::state::rocks_db::DatabaseConfig,
+) -> anyhow::Result<(
+    fuel_core::database::Database<Description>,
+    Option<DataSource<Description>>,
+)>
+where
+    Description: DatabaseDescription,
+    fuel_core::database::Database<Description>: fuel_core_storage::StorageInspect<
+            MetadataTable<Description>,
+            Error = StorageError,
+        >,
+{
+    if !enabled {
+        return Ok((Default::default(), None));
+    }
+
+    let source = Arc::new(
+        HistoricalRocksDB::<Description>::default_open(
+            path,
+            state_rewind_policy,
+            db_config,
+        )
+        .map_err(Into::<anyhow::Error>::into)
+        .with_context(|| {
+            format!(
+                "Failed to open the {} database at {path:?}",
+                Description::name()
+            )
+        })?,
+    );
+
+    let data_source: DataSourceType<Description> = source.clone();
+
+    Ok((
+        <fuel_core::database::Database<Description>>::new(data_source),
+        Some(source),
+    ))
+}
+
+/// Reverse changes stored in the modifications history of `source` for every height
+/// above `height`
+fn history_above<Description>(
+    source: &HistoricalRocksDB<Description>,
+    height: u64,
+) -> anyhow::Result<BTreeMap<u64, Changes>>
+where
+    Description: DatabaseDescription,
+{
+    let history = source
+        .latest_view()
+        .create_snapshot_generic::<Historical<Description>>();
+
+    let mut changes = BTreeMap::new();
+    for entry in history
+        .iter_all::<ModificationsHistoryV2<Description>>(Some(IterDirection::Reverse))
+    {
+        let (modified_at, reverse_changes) = entry?;
+        if modified_at <= height {
+            break;
+        }
+        changes.insert(modified_at, reverse_changes);
+    }
+
+    // the first version of the history isn't ordered by height, and is only read
+    // for the heights the migrated history doesn't have
+    for entry in history.iter_all::<ModificationsHistoryV1<Description>>(None) {
+        let (modified_at, reverse_changes) = entry?;
+        if modified_at > height {
+            changes.entry(modified_at).or_insert(reverse_changes);
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Reads `key` from the historical view of `database` at `height`
+fn read_at<Description>(
+    database: &fuel_core::database::Database<Description>,
+    height: &Description::Height,
+    column: Description::Column,
+    key: &[u8],
+) -> anyhow::Result<Option<Value>>
+where
+    Description: DatabaseDescription,
+{
+    // without a height the view would silently fall back to the latest state
+    if database.latest_height().is_none() {
+        anyhow::bail!(
+            "The {} database has no height, so it has no history to read",
+            Description::name()
+        );
+    }
+
+    Ok(database.view_at(height)?.get(key, column)?)
 }
 
 impl DatabaseHandle {
     /// Create a new database handle
     pub fn try_new(variant: Database, config: DatabaseConfig) -> anyhow::Result<Self> {
-        let database = Self::db(variant, &config)?;
+        Self::try_new_with(variant, &[], config)
+    }
+
+    /// Create a new database handle which also opens the `auxiliary` databases
+    /// from the same database path
+    pub fn try_new_with(
+        variant: Database,
+        auxiliary: &[Database],
+        config: DatabaseConfig,
+    ) -> anyhow::Result<Self> {
+        let variants: Vec<_> = std::iter::once(variant)
+            .chain(auxiliary.iter().copied())
+            .collect();
+        let (database, sources) =
+            Self::db(&variants, &config, StateRewindPolicy::NoRewind)?;
+        Ok(Self {
+            variant,
+            config,
+            database,
+            sources,
+        })
+    }
+
+    /// Create a new database handle with the state history enabled, so the databases
+    /// can be read and rolled back at past heights
+    pub fn try_new_historical(
+        variant: Database,
+        auxiliary: &[Database],
+        config: DatabaseConfig,
+    ) -> anyhow::Result<Self> {
+        let variants: Vec<_> = std::iter::once(variant)
+            .chain(auxiliary.iter().copied())
+            .collect();
+        let (database, sources) =
+            Self::db(&variants, &config, StateRewindPolicy::RewindFullRange)?;
         Ok(Self {
             variant,
             config,
             database,
+            sources,
         })
     }
 
@@ -89,96 +280,165 @@ impl DatabaseHandle {
     }
 
     fn db(
-        variant: Database,
+        variants: &[Database],
         config: &DatabaseConfig,
-    ) -> anyhow::Result<CombinedDatabase> {
+        state_rewind_policy: StateRewindPolicy,
+    ) -> anyhow::Result<(CombinedDatabase, DataSources)> {
         let path = PathBuf::from_str(&config.path)?;
-        // TODO: make configurable
-        let state_rewind_policy = StateRewindPolicy::NoRewind;
         let db_config = fuel_core::state::rocks_db::DatabaseConfig {
             cache_capacity: None,
             max_fds: -1,
             columns_policy: ColumnsPolicy::Lazy,
         };
 
-        // only open the variant's database, rest are in memory
+        // only open the requested databases, rest are in memory
         // TODO: in repl mode, maybe we want to open all databases, using CombinedDatabase::open
-        let res = match variant {
-            Database::OnChain => {
-                let db = fuel_core::database::Database::open_rocksdb(
-                    &path,
-                    state_rewind_policy,
-                    db_config,
-                )?;
-
-                CombinedDatabase::new(
-                    db,
-                    Default::default(),
-                    Default::default(),
-                    Default::default(),
-                    Default::default(),
-                )
-            }
-            Database::OffChain => {
-                let db = fuel_core::database::Database::open_rocksdb(
-                    &path,
-                    state_rewind_policy,
-                    db_config,
-                )?;
+        let (on_chain, on_chain_source) = open_database_if(
+            variants.contains(&Database::OnChain),
+            &path,
+            state_rewind_policy,
+            db_config,
+        )?;
+        let (off_chain, off_chain_source) = open_database_if(
+            variants.contains(&Database::OffChain),
+            &path,
+            state_rewind_policy,
+            db_config,
+        )?;
+        let (relayer, relayer_source) = open_database_if(
+            variants.contains(&Database::Relayer),
+            &path,
+            state_rewind_policy,
+            db_config,
+        )?;
+        let (gas_price, gas_price_source) = open_database_if(
+            variants.contains(&Database::GasPrice),
+            &path,
+            state_rewind_policy,
+            db_config,
+        )?;
+        let (compression, compression_source) = open_database_if(
+            variants.contains(&Database::Compression),
+            &path,
+            state_rewind_policy,
+            db_config,
+        )?;
 
-                CombinedDatabase::new(
-                    Default::default(),
-                    db,
-                    Default::default(),
-                    Default::default(),
-                    Default::default(),
-                )
-            }
-            Database::Relayer => {
-                let db = fuel_core::database::Database::open_rocksdb(
-                    &path,
-                    state_rewind_policy,
-                    db_config,
-                )?;
-                CombinedDatabase::new(
-                    Default::default(),
-                    Default::default(),
-                    db,
-                    Default::default(),
-                    Default::default(),
-                )
-            }
-            Database::GasPrice => {
-                let db = fuel_core::database::Database::open_rocksdb(
-                    &path,
-                    state_rewind_policy,
-                    db_config,
-                )?;
-                CombinedDatabase::new(
-                    Default::default(),
-                    Default::default(),
-                    Default::default(),
-                    db,
-                    Default::default(),
-                )
-            }
-            Database::Compression => {
-                let db = fuel_core::database::Database::open_rocksdb(
-                    &path,
-                    state_rewind_policy,
-                    db_config,
-                )?;
-                CombinedDatabase::new(
-                    Default::default(),
-                    Default::default(),
-                    Default::default(),
-                    Default::default(),
-                    db,
-                )
-            }
+        let database =
+            CombinedDatabase::new(on_chain, off_chain, relayer, gas_price, compression);
+        let sources = DataSources {
+            on_chain: on_chain_source,
+            off_chain: off_chain_source,
+            relayer: relayer_source,
+            gas_price: gas_price_source,
+            compression: compression_source,
         };
 
-        Ok(res)
+        Ok((database, sources))
+    }
+
+    /// Get the underlying combined database
+    pub const fn database(&self) -> &CombinedDatabase {
+        &self.database
+    }
+
+    /// Commit raw changes to one of the opened databases.
+    ///
+    /// The changes bypass the height tracking of the database, so they must not
+    /// contain entries that advance the height.
+    pub fn commit_changes(
+        &self,
+        database: Database,
+        changes: Changes,
+    ) -> anyhow::Result<()> {
+        fn not_opened(database: Database) -> anyhow::Error {
+            anyhow::anyhow!("{database:?} database is not opened")
+        }
+
+        match database {
+            Database::OnChain => self
+                .sources
+                .on_chain
+                .as_ref()
+                .ok_or_else(|| not_opened(database))?
+                .commit_changes(None, changes.into())?,
+            Database::OffChain => self
+                .sources
+                .off_chain
+                .as_ref()
+                .ok_or_else(|| not_opened(database))?
+                .commit_changes(None, changes.into())?,
+            Database::Compression => self
+                .sources
+                .compression
+                .as_ref()
+                .ok_or_else(|| not_opened(database))?
+                .commit_changes(None, changes.into())?,
+            Database::GasPrice => self
+                .sources
+                .gas_price
+                .as_ref()
+                .ok_or_else(|| not_opened(database))?
+                .commit_changes(None, changes.into())?,
+            Database::Relayer => self
+                .sources
+                .relayer
+                .as_ref()
+                .ok_or_else(|| not_opened(database))?
+                .commit_changes(None, changes.into())?,
+        }
+
+        Ok(())
+    }
+
+    /// Reverse changes stored in the modifications history of one of the opened
+    /// databases for every height above `height`, keyed by the height that caused them
+    pub fn modifications_history(
+        &self,
+        database: Database,
+        height: u64,
+    ) -> anyhow::Result<BTreeMap<u64, Changes>> {
+        fn not_opened(database: Database) -> anyhow::Error {
+            anyhow::anyhow!("{database:?} database is not opened")
+        }
+
+        match database {
+            Database::OnChain => history_above(
+                self.sources
+                    .on_chain
+                    .as_ref()
+                    .ok_or_else(|| not_opened(database))?,
+                height,
+            ),
+            Database::OffChain => history_above(
+                self.sources
+                    .off_chain
+                    .as_ref()
+                    .ok_or_else(|| not_opened(database))?,
+                height,
+            ),
+            Database::Compression => history_above(
+                self.sources
+                    .compression
+                    .as_ref()
+                    .ok_or_else(|| not_opened(database))?,
+                height,
+            ),
+            Database::GasPrice => history_above(
+                self.sources
+                    .gas_price
+                    .as_ref()
+                    .ok_or_else(|| not_opened(database))?,
+                height,
+            ),
+            Database::Relayer => history_above(
+                self.sources
+                    .relayer
+                    .as_ref()
+                    .ok_or_else(|| not_opened(database))?,
+                height,
+            ),
+        }
     }
 
     /// Perform a read operation on the database
@@ -229,6 +489,74 @@ impl DatabaseHandle {
         Ok(maybe_value)
     }
 
+    /// Perform a read operation on the database as of `height`, a DA height for the
+    /// relayer database.
+    ///
+    /// The value comes from the historical view, so the database must store the
+    /// modifications history of that height.
+    pub fn perform_read_at(
+        &self,
+        column: &Column,
+        key: &[u8],
+        height: u64,
+    ) -> anyhow::Result<Option<Value>> {
+        let block_height = || -> anyhow::Result<BlockHeight> {
+            Ok(u32::try_from(height)
+                .map_err(|_| anyhow::anyhow!("Block height {height} is out of range"))?
+                .into())
+        };
+
+        let maybe_value = match self.variant() {
+            Database::OnChain => read_at(
+                self.database.on_chain(),
+                &block_height()?,
+                (*column
+                    .as_onchain()
+                    .ok_or_else(|| anyhow::anyhow!("invalid variant"))?)
+                .into(),
+                key,
+            )?,
+            Database::OffChain => read_at(
+                self.database.off_chain(),
+                &block_height()?,
+                (*column
+                    .as_offchain()
+                    .ok_or_else(|| anyhow::anyhow!("invalid variant"))?)
+                .into(),
+                key,
+            )?,
+            Database::Compression => read_at(
+                self.database.compression(),
+                &block_height()?,
+                (*column
+                    .as_compression()
+                    .ok_or_else(|| anyhow::anyhow!("invalid variant"))?)
+                .into(),
+                key,
+            )?,
+            Database::GasPrice => read_at(
+                self.database.gas_price(),
+                &block_height()?,
+                (*column
+                    .as_gas_price()
+                    .ok_or_else(|| anyhow::anyhow!("invalid variant"))?)
+                .into(),
+                key,
+            )?,
+            Database::Relayer => read_at(
+                self.database.relayer(),
+                &DaBlockHeight::from(height),
+                (*column
+                    .as_relayer()
+                    .ok_or_else(|| anyhow::anyhow!("invalid variant"))?)
+                .into(),
+                key,
+            )?,
+        };
+
+        Ok(maybe_value)
+    }
+
     /// Perform a write operation on the database
     pub fn perform_write(
         &mut self,
@@ -6,10 +6,14 @@
 #![deny(missing_docs)]
 #![deny(warnings)]
 
+pub mod chain;
+pub mod checks;
 pub mod cli;
 pub mod columns;
 pub mod databases;
 pub mod printer;
+pub mod queries;
+pub mod rollback;
 
 use cli::ValidatedFuelCoreInspectorCliArgs;
 use databases::DatabaseHandle;
@@ -61,6 +65,13 @@ impl TryFrom<&ValidatedFuelCoreInspectorCliArgs> for DatabaseHandle {
     type Error = anyhow::Error;
 
     fn try_from(value: &ValidatedFuelCoreInspectorCliArgs) -> Result<Self, Self::Error> {
+        if value.at_height().is_some() {
+            return Self::try_new_historical(
+                *value.database(),
+                &[],
+                value.database_config().clone(),
+            );
+        }
         Self::try_new(*value.database(), value.database_config().clone())
     }
 }
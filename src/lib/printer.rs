@@ -1,4 +1,4 @@
-//! simple hex utils
+//! simple hex and json utils
 
 use fuel_core_storage::kv_store::Value;
 use pretty_hex::{
@@ -27,3 +27,101 @@ impl PrintHexExt for Option<Value> {
         }
     }
 }
+
+/// JSON printing extension trait
+pub trait PrintJsonExt {
+    /// Print the value as pretty JSON
+    fn print_json(&self) -> anyhow::Result<()>;
+}
+
+impl<T: serde::Serialize> PrintJsonExt for T {
+    fn print_json(&self) -> anyhow::Result<()> {
+        println!("{}", serde_json::to_string_pretty(self)?);
+        Ok(())
+    }
+}
+
+/// Format bytes as a hex string, the same way ids are displayed
+pub fn hex_string(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len().saturating_mul(2));
+    for byte in bytes {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    hex
+}
+
+/// A single field that differs between two JSON values
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// dotted path of the field, with array indexes in brackets
+    pub path: String,
+    /// value before the change, `null` if the field was added
+    pub before: serde_json::Value,
+    /// value after the change, `null` if the field was removed
+    pub after: serde_json::Value,
+}
+
+impl core::fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {} -> {}", self.path, self.before, self.after)
+    }
+}
+
+/// Compare two JSON values field by field and return the leaves that differ
+pub fn diff_json(
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+) -> Vec<FieldChange> {
+    fn walk(
+        path: String,
+        before: &serde_json::Value,
+        after: &serde_json::Value,
+        changes: &mut Vec<FieldChange>,
+    ) {
+        use serde_json::Value;
+
+        let join = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{path}.{key}")
+            }
+        };
+
+        match (before, after) {
+            (Value::Object(before), Value::Object(after)) => {
+                let keys = before
+                    .keys()
+                    .chain(after.keys().filter(|key| !before.contains_key(*key)));
+                for key in keys {
+                    walk(
+                        join(key),
+                        before.get(key).unwrap_or(&Value::Null),
+                        after.get(key).unwrap_or(&Value::Null),
+                        changes,
+                    );
+                }
+            }
+            (Value::Array(before), Value::Array(after)) => {
+                for index in 0..before.len().max(after.len()) {
+                    walk(
+                        format!("{path}[{index}]"),
+                        before.get(index).unwrap_or(&Value::Null),
+                        after.get(index).unwrap_or(&Value::Null),
+                        changes,
+                    );
+                }
+            }
+            (before, after) if before != after => changes.push(FieldChange {
+                path,
+                before: before.clone(),
+                after: after.clone(),
+            }),
+            _ => {}
+        }
+    }
+
+    let mut changes = vec![];
+    walk(String::new(), before, after, &mut changes);
+    changes
+}
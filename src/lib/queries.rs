@@ -0,0 +1,16 @@
+//! Lookups that decode related entries across the databases
+
+pub mod assets;
+pub mod block;
+pub mod consensus_parameters;
+pub mod contract;
+pub mod evictor;
+pub mod extract;
+pub mod gas_price;
+pub mod genesis;
+pub mod owner;
+pub mod registry;
+pub mod relayed_transactions;
+pub mod relayer;
+pub mod state_transition_bytecodes;
+pub mod transaction;
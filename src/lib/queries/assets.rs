@@ -0,0 +1,89 @@
+//! List the assets indexed in the off-chain `AssetsInfo`
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::assets::{
+        AssetDetails,
+        AssetsInfo,
+    },
+    types::fuel_types::AssetId,
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::IteratorOverTable,
+};
+
+use crate::{
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+};
+
+/// Assets command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct AssetsArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Only print this asset
+    asset_id: Option<AssetId>,
+}
+
+impl AssetsArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the assets command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle =
+            DatabaseHandle::try_new(Database::OffChain, self.database_config.clone())?;
+
+        let result = AssetEntry::list(&database_handle, self.asset_id)
+            .and_then(|entries| entries.print_json());
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// A decoded `AssetsInfo` entry
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AssetEntry {
+    /// id of the asset
+    pub asset_id: AssetId,
+    /// contract, sub id and total supply of the asset
+    #[serde(flatten)]
+    pub details: AssetDetails,
+}
+
+impl AssetEntry {
+    /// Decode every entry of `AssetsInfo`, or only `asset_id`
+    pub fn list(
+        database_handle: &DatabaseHandle,
+        asset_id: Option<AssetId>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let off_chain = database_handle.database().off_chain();
+
+        if let Some(asset_id) = asset_id {
+            let details = off_chain
+                .storage::<AssetsInfo>()
+                .get(&asset_id)?
+                .ok_or_else(|| anyhow::anyhow!("Asset {asset_id} not found"))?
+                .into_owned();
+            return Ok(vec![Self { asset_id, details }]);
+        }
+
+        let entries = off_chain
+            .iter_all::<AssetsInfo>(None)
+            .map(|entry| entry.map(|(asset_id, details)| Self { asset_id, details }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,205 @@
+//! Look up a block by height or id with its consensus and transactions
+
+use core::str::FromStr;
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::{
+        blocks::FuelBlockIdsToHeights,
+        old::{
+            OldFuelBlockConsensus,
+            OldFuelBlocks,
+            OldTransactions,
+        },
+    },
+    types::{
+        blockchain::{
+            block::CompressedBlock,
+            consensus::Consensus,
+            primitives::BlockId,
+        },
+        fuel_tx::{
+            Transaction,
+            TxId,
+        },
+        fuel_types::BlockHeight,
+    },
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    tables::{
+        FuelBlocks,
+        SealedBlockConsensus,
+        Transactions,
+    },
+};
+
+use crate::{
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+};
+
+/// Block lookup command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct BlockArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Height or id of the block
+    block: BlockRef,
+
+    /// Inline the full transactions instead of their ids
+    #[arg(long)]
+    transactions: bool,
+}
+
+impl BlockArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the block lookup
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let lookup = BlockLookup::find(&database_handle, self.block, self.transactions);
+        database_handle.shutdown();
+
+        lookup?.print_json()
+    }
+}
+
+/// A block referenced either by height or by id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockRef {
+    /// height of the block
+    Height(BlockHeight),
+    /// id of the block
+    Id(BlockId),
+}
+
+impl FromStr for BlockRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(height) = s.parse::<u32>() {
+            return Ok(Self::Height(height.into()));
+        }
+
+        BlockId::from_str(s).map(Self::Id).map_err(|_| {
+            anyhow::anyhow!("Expected a block height or a block id, got `{s}`")
+        })
+    }
+}
+
+/// A transaction of the block with its body, if stored
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct InlinedTransaction {
+    /// id of the transaction
+    pub id: TxId,
+    /// the decoded transaction
+    pub transaction: Option<Transaction>,
+}
+
+/// Everything known about a block
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BlockLookup {
+    /// height of the block
+    pub height: BlockHeight,
+    /// id of the block
+    pub id: BlockId,
+    /// table the block is stored in
+    pub table: &'static str,
+    /// the decoded block with its header and transaction ids
+    pub block: CompressedBlock,
+    /// consensus of the block
+    pub consensus: Option<Consensus>,
+    /// full transactions of the block, if requested
+    pub transactions: Option<Vec<InlinedTransaction>>,
+}
+
+impl BlockLookup {
+    /// Resolve the block through `FuelBlockIdsToHeights` and load it from `FuelBlocks`
+    /// or `OldFuelBlocks`, with its consensus and optionally its transactions
+    pub fn find(
+        database_handle: &DatabaseHandle,
+        block: BlockRef,
+        inline_transactions: bool,
+    ) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let height = match block {
+            BlockRef::Height(height) => height,
+            BlockRef::Id(id) => off_chain
+                .storage::<FuelBlockIdsToHeights>()
+                .get(&id)?
+                .map(|height| *height)
+                .ok_or_else(|| anyhow::anyhow!("Block id {id} is not indexed"))?,
+        };
+
+        let (table, block, consensus) =
+            match on_chain.storage::<FuelBlocks>().get(&height)? {
+                Some(block) => (
+                    "FuelBlocks",
+                    block.into_owned(),
+                    on_chain
+                        .storage::<SealedBlockConsensus>()
+                        .get(&height)?
+                        .map(|consensus| consensus.into_owned()),
+                ),
+                None => match off_chain.storage::<OldFuelBlocks>().get(&height)? {
+                    Some(block) => (
+                        "OldFuelBlocks",
+                        block.into_owned(),
+                        off_chain
+                            .storage::<OldFuelBlockConsensus>()
+                            .get(&height)?
+                            .map(|consensus| consensus.into_owned()),
+                    ),
+                    None => anyhow::bail!("Block at height {} not found", *height),
+                },
+            };
+
+        let transactions = inline_transactions
+            .then(|| {
+                block
+                    .transactions()
+                    .iter()
+                    .map(|id| {
+                        let transaction =
+                            match on_chain.storage::<Transactions>().get(id)? {
+                                Some(tx) => Some(tx.into_owned()),
+                                None => off_chain
+                                    .storage::<OldTransactions>()
+                                    .get(id)?
+                                    .map(|tx| tx.into_owned()),
+                            };
+                        Ok(InlinedTransaction {
+                            id: *id,
+                            transaction,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+            })
+            .transpose()?;
+
+        Ok(Self {
+            height,
+            id: block.header().id(),
+            table,
+            block,
+            consensus,
+            transactions,
+        })
+    }
+}
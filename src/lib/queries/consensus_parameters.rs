@@ -0,0 +1,145 @@
+//! List, decode and diff the consensus parameters versions
+
+use fuel_core::types::{
+    blockchain::header::{
+        BlockHeader,
+        ConsensusParametersVersion,
+    },
+    fuel_tx::ConsensusParameters,
+    fuel_types::BlockHeight,
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::ConsensusParametersVersions,
+};
+
+use crate::{
+    chain::first_heights,
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::{
+        PrintJsonExt,
+        diff_json,
+    },
+};
+
+/// Consensus parameters command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct ConsensusParametersArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Only print this version
+    #[arg(long, conflicts_with = "diff")]
+    version: Option<ConsensusParametersVersion>,
+
+    /// Print the field-level differences between two versions instead
+    #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+    diff: Option<Vec<ConsensusParametersVersion>>,
+}
+
+impl ConsensusParametersArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the consensus parameters command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OnChain,
+            &[Database::OffChain],
+            self.database_config.clone(),
+        )?;
+
+        let result = match self.diff.as_deref() {
+            Some(&[from, to]) => print_diff(&database_handle, from, to),
+            _ => ConsensusParametersEntry::list(&database_handle, self.version)
+                .and_then(|entries| entries.print_json()),
+        };
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// A decoded consensus parameters version
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ConsensusParametersEntry {
+    /// version of the consensus parameters
+    pub version: ConsensusParametersVersion,
+    /// height of the first stored block using this version
+    pub first_height: Option<BlockHeight>,
+    /// the decoded consensus parameters
+    pub parameters: ConsensusParameters,
+}
+
+impl ConsensusParametersEntry {
+    /// Decode every version stored in `ConsensusParametersVersions`, or only `version`,
+    /// and find the first block using each of them
+    pub fn list(
+        database_handle: &DatabaseHandle,
+        version: Option<ConsensusParametersVersion>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let first_heights =
+            first_heights(database_handle, BlockHeader::consensus_parameters_version)?;
+
+        let entries = database_handle
+            .database()
+            .on_chain()
+            .iter_all::<ConsensusParametersVersions>(None)
+            .filter(|entry| {
+                version.is_none()
+                    || entry.as_ref().is_ok_and(|(v, _)| Some(*v) == version)
+            })
+            .map(|entry| {
+                entry.map(|(version, parameters)| Self {
+                    version,
+                    first_height: first_heights.get(&version).copied(),
+                    parameters,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let (Some(version), true) = (version, entries.is_empty()) {
+            anyhow::bail!("Consensus parameters version {version} not found");
+        }
+
+        Ok(entries)
+    }
+}
+
+fn print_diff(
+    database_handle: &DatabaseHandle,
+    from: ConsensusParametersVersion,
+    to: ConsensusParametersVersion,
+) -> anyhow::Result<()> {
+    let load = |version| -> anyhow::Result<serde_json::Value> {
+        let parameters = database_handle
+            .database()
+            .on_chain()
+            .storage::<ConsensusParametersVersions>()
+            .get(&version)?
+            .ok_or_else(|| {
+                anyhow::anyhow!("Consensus parameters version {version} not found")
+            })?;
+        Ok(serde_json::to_value(parameters.as_ref())?)
+    };
+
+    let changes = diff_json(&load(from)?, &load(to)?);
+    for change in &changes {
+        println!("{change}");
+    }
+    println!(
+        "Found {} changed field(s) between versions {from} and {to}",
+        changes.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+//! Report the info, code, assets and storage of a contract
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::contracts::ContractsInfo,
+    types::{
+        entities::contract::ContractUtxoInfo,
+        fuel_crypto::Hasher,
+        fuel_tx::Contract,
+        fuel_types::{
+            AssetId,
+            Bytes32,
+            ContractId,
+            Salt,
+        },
+    },
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::{
+        ContractsAssets,
+        ContractsLatestUtxo,
+        ContractsRawCode,
+        ContractsState,
+    },
+};
+
+use crate::{
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::{
+        PrintJsonExt,
+        hex_string,
+    },
+};
+
+/// Contract report command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct ContractArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Id of the contract
+    contract_id: ContractId,
+
+    /// Dump every storage slot of the contract
+    #[arg(long)]
+    state: bool,
+
+    /// Only dump the storage slots whose key starts with this hex prefix
+    #[arg(long, requires = "state")]
+    state_prefix: Option<String>,
+}
+
+impl ContractArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the contract report
+    pub fn run(&self) -> anyhow::Result<()> {
+        let state_prefix = self
+            .state_prefix
+            .as_ref()
+            .map(crate::cli::hex_string_to_bytes)
+            .transpose()?;
+
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OnChain,
+            &[Database::OffChain],
+            self.database_config.clone(),
+        )?;
+
+        let result = ContractReport::compute(&database_handle, self.contract_id)
+            .and_then(|report| report.print_json())
+            .and_then(|()| {
+                if self.state {
+                    dump_state(
+                        &database_handle,
+                        self.contract_id,
+                        state_prefix.as_deref().unwrap_or_default(),
+                    )
+                } else {
+                    Ok(())
+                }
+            });
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// Balance of a single asset held by the contract
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ContractAsset {
+    /// id of the asset
+    pub asset_id: AssetId,
+    /// balance of the asset
+    pub amount: u64,
+}
+
+/// Everything known about a contract, except its storage
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ContractReport {
+    /// id of the contract
+    pub contract_id: ContractId,
+    /// salt from `ContractsInfo`
+    pub salt: Option<Salt>,
+    /// latest UTXO of the contract
+    pub latest_utxo: Option<ContractUtxoInfo>,
+    /// size of the bytecode in bytes
+    pub code_size: Option<usize>,
+    /// code root of the bytecode
+    pub code_root: Option<Bytes32>,
+    /// sha256 hash of the bytecode
+    pub code_hash: Option<Bytes32>,
+    /// asset balances stored for the contract
+    pub assets: Vec<ContractAsset>,
+}
+
+impl ContractReport {
+    /// Collect the entries for the contract from `ContractsInfo`, `ContractsLatestUtxo`,
+    /// `ContractsRawCode` and `ContractsAssets`
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        contract_id: ContractId,
+    ) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let salt = off_chain
+            .storage::<ContractsInfo>()
+            .get(&contract_id)?
+            .map(|info| *info.salt());
+
+        let latest_utxo = on_chain
+            .storage::<ContractsLatestUtxo>()
+            .get(&contract_id)?
+            .map(|utxo| utxo.into_owned());
+
+        let code = on_chain.storage::<ContractsRawCode>().get(&contract_id)?;
+        let code_size = code.as_ref().map(|code| code.as_ref().len());
+        let code_root = code
+            .as_ref()
+            .map(|code| Contract::root_from_code(code.as_ref()));
+        let code_hash = code.as_ref().map(|code| Hasher::hash(code.as_ref()));
+
+        let assets = on_chain
+            .iter_all_by_prefix::<ContractsAssets, _>(Some(contract_id))
+            .map(|entry| {
+                entry.map(|(key, amount)| ContractAsset {
+                    asset_id: *key.asset_id(),
+                    amount,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if salt.is_none() && latest_utxo.is_none() && code.is_none() && assets.is_empty()
+        {
+            anyhow::bail!("Contract {contract_id} not found");
+        }
+
+        Ok(Self {
+            contract_id,
+            salt,
+            latest_utxo,
+            code_size,
+            code_root,
+            code_hash,
+            assets,
+        })
+    }
+}
+
+/// Print every storage slot of the contract whose key starts with `prefix`,
+/// one `<key> <value>` line per slot, without buffering the whole state
+pub fn dump_state(
+    database_handle: &DatabaseHandle,
+    contract_id: ContractId,
+    prefix: &[u8],
+) -> anyhow::Result<()> {
+    let on_chain = database_handle.database().on_chain();
+
+    let mut full_prefix = contract_id.to_vec();
+    full_prefix.extend_from_slice(prefix);
+
+    let mut slots = 0usize;
+    for entry in on_chain.iter_all_by_prefix::<ContractsState, _>(Some(full_prefix)) {
+        let (key, value) = entry?;
+        println!("{} {}", key.state_key(), hex_string(&value.0));
+        slots = slots.saturating_add(1);
+    }
+    eprintln!("Dumped {slots} storage slot(s)");
+
+    Ok(())
+}
@@ -0,0 +1,242 @@
+//! Decode the compression `EvictorCache` and `Timestamps` and simulate the next
+//! registry key evictions
+
+use fuel_core::types::fuel_compression::RegistryKey;
+use fuel_core_compression::VersionedBlockPayload;
+use fuel_core_compression_service::storage::{
+    self,
+    timestamps::TimestampKey,
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::{
+        IterDirection,
+        IteratorOverTable,
+    },
+};
+
+use crate::{
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+    queries::registry::{
+        RegistryEntry,
+        RegistryTable,
+    },
+};
+
+/// Evictor command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct EvictorArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Only decode this registry table
+    #[arg(long, value_enum)]
+    table: Option<RegistryTable>,
+
+    /// Number of upcoming evictions to simulate per table
+    #[arg(long, default_value_t = 1)]
+    simulate: u32,
+
+    /// Registry retention of the node in seconds, to flag evictions of keys that are
+    /// still retained
+    #[arg(long)]
+    retention: Option<u64>,
+
+    /// Include the timestamp of every key
+    #[arg(long)]
+    timestamps: bool,
+}
+
+impl EvictorArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the evictor command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle =
+            DatabaseHandle::try_new(Database::Compression, self.database_config.clone())?;
+
+        let result = EvictorReport::compute(
+            &database_handle,
+            self.table,
+            self.simulate,
+            self.retention,
+            self.timestamps,
+        )
+        .and_then(|report| report.print_json());
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// A registry key the evictor will assign next
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Eviction {
+    /// the registry key
+    pub key: u32,
+    /// value currently stored at the key, as hex, `None` if the key is free
+    pub value: Option<String>,
+    /// unix time the key was last written at
+    pub timestamp: Option<i64>,
+    /// seconds between the write and the latest compressed block
+    pub age: Option<i64>,
+    /// whether the key is still inside the retention, only if it was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retained: Option<bool>,
+}
+
+/// Timestamp of a registry key
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct KeyTimestamp {
+    /// the registry key
+    pub key: u32,
+    /// unix time the key was last written at
+    pub timestamp: i64,
+}
+
+/// Evictor state of a single registry table
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TableEvictor {
+    /// the registry table
+    pub table: RegistryTable,
+    /// latest assigned key stored in `EvictorCache`
+    pub latest_assigned_key: Option<u32>,
+    /// number of keys with a timestamp
+    pub keys: usize,
+    /// the upcoming evictions, the first one is the next key
+    pub evictions: Vec<Eviction>,
+    /// timestamps of all keys, only if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamps: Option<Vec<KeyTimestamp>>,
+}
+
+/// Decoded `EvictorCache` and `Timestamps`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EvictorReport {
+    /// unix time of the latest compressed block, the reference for the ages
+    pub latest_block_time: Option<i64>,
+    /// evictor state per table
+    pub tables: Vec<TableEvictor>,
+}
+
+impl EvictorReport {
+    /// Decode the evictor state of every registry table, or only `table`, and list
+    /// the `simulate` keys it will assign next.
+    ///
+    /// The simulation walks the keys the same way the evictor does, from the key
+    /// after the latest assigned one. The compressor also skips keys already used by
+    /// the block being compressed, so a real block may shift the sequence.
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        table: Option<RegistryTable>,
+        simulate: u32,
+        retention: Option<u64>,
+        with_timestamps: bool,
+    ) -> anyhow::Result<Self> {
+        let compression = database_handle.database().compression();
+
+        let latest_block_time = compression
+            .iter_all::<storage::CompressedBlocks>(Some(IterDirection::Reverse))
+            .next()
+            .transpose()?
+            .map(|(_, block)| block.consensus_header().time.to_unix());
+
+        // number of keys and, only if requested, their timestamps per table
+        let mut keyspaces = enum_iterator::all::<RegistryTable>()
+            .map(|table| (table, 0usize, vec![]))
+            .collect::<Vec<_>>();
+        for entry in compression.iter_all::<storage::Timestamps>(None) {
+            let (TimestampKey { keyspace, key }, timestamp) = entry?;
+            let Some((_, keys, timestamps)) = keyspaces
+                .iter_mut()
+                .find(|(table, _, _)| table.timestamp_keyspace() == keyspace)
+            else {
+                continue;
+            };
+            *keys = keys.saturating_add(1);
+            if with_timestamps {
+                timestamps.push(KeyTimestamp {
+                    key: key.as_u32(),
+                    timestamp: timestamp.to_unix(),
+                });
+            }
+        }
+
+        let mut tables = vec![];
+        for current in enum_iterator::all::<RegistryTable>() {
+            if table.is_some_and(|table| table != current) {
+                continue;
+            }
+
+            let latest_assigned_key = compression
+                .storage::<storage::EvictorCache>()
+                .get(&current.metadata_key())?
+                .map(|key| key.into_owned());
+
+            if latest_assigned_key == Some(RegistryKey::DEFAULT_VALUE) {
+                anyhow::bail!(
+                    "EvictorCache holds the reserved default key for {current}"
+                );
+            }
+
+            let mut evictions = vec![];
+            let mut key =
+                latest_assigned_key.map_or(RegistryKey::ZERO, RegistryKey::next);
+            for _ in 0..simulate {
+                let value = RegistryEntry::read(database_handle, current, &key)?
+                    .map(|entry| entry.value);
+                let timestamp = compression
+                    .storage::<storage::Timestamps>()
+                    .get(&TimestampKey {
+                        keyspace: current.timestamp_keyspace(),
+                        key,
+                    })?
+                    .map(|timestamp| timestamp.to_unix());
+                let age = timestamp
+                    .zip(latest_block_time)
+                    .map(|(timestamp, latest)| latest.saturating_sub(timestamp));
+                evictions.push(Eviction {
+                    key: key.as_u32(),
+                    value,
+                    timestamp,
+                    age,
+                    retained: retention.map(|retention| {
+                        age.is_some_and(|age| {
+                            u64::try_from(age).is_ok_and(|age| age <= retention)
+                        })
+                    }),
+                });
+                key = key.next();
+            }
+
+            let (keys, timestamps) = keyspaces
+                .iter_mut()
+                .find(|(table, _, _)| *table == current)
+                .map(|(_, keys, timestamps)| (*keys, core::mem::take(timestamps)))
+                .unwrap_or_default();
+
+            tables.push(TableEvictor {
+                table: current,
+                latest_assigned_key: latest_assigned_key.map(RegistryKey::as_u32),
+                keys,
+                evictions,
+                timestamps: with_timestamps.then_some(timestamps),
+            });
+        }
+
+        Ok(Self {
+            latest_block_time,
+            tables,
+        })
+    }
+}
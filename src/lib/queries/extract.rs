@@ -0,0 +1,224 @@
+//! Extract contract bytecode, blobs and uploaded bytecode to files
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use fuel_core::{
+    fuel_core_graphql_api::storage::contracts::ContractsInfo,
+    types::{
+        fuel_merkle::binary::in_memory::MerkleTree,
+        fuel_tx::{
+            BlobIdExt,
+            Contract,
+        },
+        fuel_types::{
+            BlobId,
+            Bytes32,
+            ContractId,
+        },
+        fuel_vm::UploadedBytecode,
+    },
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    tables::{
+        BlobData,
+        ContractsRawCode,
+        UploadedBytecodes,
+    },
+};
+
+use crate::databases::{
+    Database,
+    DatabaseConfig,
+    DatabaseHandle,
+};
+
+/// Extract command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct ExtractArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Entry to extract
+    #[clap(flatten)]
+    source: ExtractSource,
+
+    /// File to write the raw bytes to
+    #[arg(long, short)]
+    out: PathBuf,
+
+    /// Initial state root the contract was deployed with, required to verify the
+    /// contract id
+    #[arg(long, requires = "contract")]
+    state_root: Option<Bytes32>,
+
+    /// Subsection size the bytecode was uploaded with, required to verify the
+    /// bytecode root of an upload
+    #[arg(long, requires = "upload")]
+    subsection_size: Option<usize>,
+}
+
+/// Entry to extract, exactly one must be set
+#[derive(clap::Args, Debug, Clone)]
+#[group(required = true, multiple = false)]
+pub struct ExtractSource {
+    /// Id of a contract in `ContractsRawCode`
+    #[arg(long)]
+    contract: Option<ContractId>,
+
+    /// Id of a blob in `Blobs`
+    #[arg(long)]
+    blob: Option<BlobId>,
+
+    /// Bytecode root of an upload in `UploadedBytecodes`
+    #[arg(long)]
+    upload: Option<Bytes32>,
+}
+
+impl ExtractArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the extraction
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OnChain,
+            &[Database::OffChain],
+            self.database_config.clone(),
+        )?;
+
+        let result = self.extract(&database_handle);
+        database_handle.shutdown();
+
+        result
+    }
+
+    fn extract(&self, database_handle: &DatabaseHandle) -> anyhow::Result<()> {
+        let bytes = self.load(database_handle)?;
+
+        std::fs::write(&self.out, &bytes)
+            .with_context(|| format!("Failed to write {}", self.out.display()))?;
+        println!("Wrote {} byte(s) to {}", bytes.len(), self.out.display());
+
+        self.verify(database_handle, &bytes)
+    }
+
+    fn load(&self, database_handle: &DatabaseHandle) -> anyhow::Result<Vec<u8>> {
+        let on_chain = database_handle.database().on_chain();
+
+        if let Some(contract_id) = &self.source.contract {
+            let code = on_chain
+                .storage::<ContractsRawCode>()
+                .get(contract_id)?
+                .ok_or_else(|| anyhow::anyhow!("Contract {contract_id} not found"))?;
+            return Ok(code.as_ref().as_ref().to_vec());
+        }
+
+        if let Some(blob_id) = &self.source.blob {
+            let blob = on_chain
+                .storage::<BlobData>()
+                .get(blob_id)?
+                .ok_or_else(|| anyhow::anyhow!("Blob {blob_id} not found"))?;
+            return Ok(blob.as_ref().as_ref().to_vec());
+        }
+
+        if let Some(root) = &self.source.upload {
+            let upload = on_chain
+                .storage::<UploadedBytecodes>()
+                .get(root)?
+                .ok_or_else(|| anyhow::anyhow!("Upload {root} not found"))?;
+            return match upload.into_owned() {
+                UploadedBytecode::Completed(bytecode) => Ok(bytecode),
+                UploadedBytecode::Uncompleted {
+                    uploaded_subsections_number,
+                    ..
+                } => Err(anyhow::anyhow!(
+                    "Upload {root} is incomplete, only {uploaded_subsections_number} subsection(s) were uploaded"
+                )),
+            };
+        }
+
+        anyhow::bail!("Exactly one of --contract, --blob or --upload must be set")
+    }
+
+    fn verify(
+        &self,
+        database_handle: &DatabaseHandle,
+        bytes: &[u8],
+    ) -> anyhow::Result<()> {
+        if let Some(contract_id) = &self.source.contract {
+            let root = Contract::root_from_code(bytes);
+            println!("Code root: {root}");
+
+            let salt = database_handle
+                .database()
+                .off_chain()
+                .storage::<ContractsInfo>()
+                .get(contract_id)?
+                .map(|info| *info.salt());
+            let Some(salt) = salt else {
+                println!("Code root not verified, the contract salt is not indexed");
+                return Ok(());
+            };
+
+            let Some(state_root) = self.state_root else {
+                println!(
+                    "Contract id not verified, pass --state-root with the initial state root the contract was deployed with"
+                );
+                return Ok(());
+            };
+            let computed = Contract::from(bytes).id(&salt, &root, &state_root);
+            if computed != *contract_id {
+                anyhow::bail!(
+                    "Contract id mismatch: expected={contract_id} computed={computed} state_root={state_root}"
+                );
+            }
+            println!("Code root verified against the contract id");
+        }
+
+        if let Some(blob_id) = &self.source.blob {
+            let computed = BlobId::compute(bytes);
+            if computed != *blob_id {
+                anyhow::bail!("Blob id mismatch: expected={blob_id} computed={computed}");
+            }
+            println!("Blob id verified");
+        }
+
+        if let Some(root) = &self.source.upload {
+            match self.subsection_size {
+                Some(subsection_size) => {
+                    let computed = bytecode_root(bytes, subsection_size)?;
+                    if computed != *root {
+                        anyhow::bail!(
+                            "Bytecode root mismatch: expected={root} computed={computed}"
+                        );
+                    }
+                    println!("Bytecode root verified");
+                }
+                None => println!(
+                    "Bytecode root not verified, pass --subsection-size to verify it"
+                ),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Compute the bytecode root of `bytecode` uploaded in subsections of `subsection_size` bytes
+pub fn bytecode_root(bytecode: &[u8], subsection_size: usize) -> anyhow::Result<Bytes32> {
+    if subsection_size == 0 {
+        anyhow::bail!("Subsection size must be positive");
+    }
+
+    let mut tree = MerkleTree::new();
+    for subsection in bytecode.chunks(subsection_size) {
+        tree.push(subsection);
+    }
+
+    Ok(tree.root().into())
+}
@@ -0,0 +1,868 @@
+//! Decode the gas price service state and replay the gas price algorithm
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    num::NonZeroU64,
+    str::FromStr,
+};
+
+use fuel_core::{
+    service::config::GasPriceConfig,
+    types::{
+        blockchain::block::Block,
+        fuel_tx::Transaction,
+        fuel_types::BlockHeight,
+    },
+};
+use fuel_core_gas_price_service::{
+    common::{
+        fuel_core_storage_adapter::{
+            get_block_info,
+            storage::{
+                GasPriceMetadata,
+                RecordedHeights,
+                UnrecordedBlocksTable,
+            },
+        },
+        updater_metadata::UpdaterMetadata,
+        utils::BlockInfo,
+    },
+    v1::metadata::{
+        V1AlgorithmConfig,
+        v1_algorithm_from_metadata,
+    },
+};
+use fuel_core_storage::{
+    StorageAsMut,
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::{
+        ConsensusParametersVersions,
+        FuelBlocks,
+        Transactions,
+    },
+    transactional::ReadTransaction,
+};
+use fuel_gas_price_algorithm::{
+    v0::AlgorithmUpdaterV0,
+    v1::AlgorithmUpdaterV1,
+};
+
+use crate::{
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+};
+
+/// Gas price command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct GasPriceArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Only print the metadata stored for this height
+    #[arg(long, conflicts_with_all = ["unrecorded", "replay"])]
+    height: Option<u32>,
+
+    /// Print the blocks not yet recorded on the DA chain and the latest recorded height
+    #[arg(long, conflicts_with = "replay")]
+    unrecorded: bool,
+
+    /// Replay the algorithm over the blocks between FROM and TO, starting from the
+    /// metadata stored for FROM - 1
+    #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+    replay: Option<Vec<u32>>,
+
+    /// Change a field of the metadata stored for `--height`, as `<FIELD>=<VALUE>`
+    #[arg(long, value_name = "FIELD=VALUE", requires = "height")]
+    set: Vec<FieldAssignment>,
+
+    /// Actually write the edited metadata instead of only previewing it
+    #[arg(long, requires = "set")]
+    yes: bool,
+
+    /// Node configuration of the algorithm, used by the replay
+    #[clap(flatten)]
+    algorithm: GasPriceAlgorithmArgs,
+}
+
+/// Configuration of the V1 gas price algorithm which isn't part of its metadata,
+/// defaults are taken from fuel-core's `GasPriceConfig::local_node`
+#[derive(clap::Args, Debug, Clone)]
+pub struct GasPriceAlgorithmArgs {
+    /// Lowest execution gas price
+    #[arg(long, default_value_t = GasPriceConfig::local_node().min_exec_gas_price)]
+    min_exec_gas_price: u64,
+
+    /// Percentage the execution gas price changes by per block
+    #[arg(long, default_value_t = GasPriceConfig::local_node().exec_gas_price_change_percent)]
+    exec_gas_price_change_percent: u16,
+
+    /// Block fullness percentage above which the execution gas price increases
+    #[arg(long, default_value_t = GasPriceConfig::local_node().exec_gas_price_threshold_percent)]
+    l2_block_fullness_threshold_percent: u8,
+
+    /// Lowest DA gas price
+    #[arg(long, default_value_t = GasPriceConfig::local_node().min_da_gas_price)]
+    min_da_gas_price: u64,
+
+    /// Highest DA gas price
+    #[arg(long, default_value_t = GasPriceConfig::local_node().max_da_gas_price)]
+    max_da_gas_price: u64,
+
+    /// Percentage the DA gas price can change by per block
+    #[arg(long, default_value_t = GasPriceConfig::local_node().max_da_gas_price_change_percent)]
+    max_da_gas_price_change_percent: u16,
+
+    /// Proportional component of the DA gas price controller
+    #[arg(long, default_value_t = GasPriceConfig::local_node().da_gas_price_p_component, allow_negative_numbers = true)]
+    da_p_component: i64,
+
+    /// Derivative component of the DA gas price controller
+    #[arg(long, default_value_t = GasPriceConfig::local_node().da_gas_price_d_component, allow_negative_numbers = true)]
+    da_d_component: i64,
+
+    /// Number of blocks in the normal activity range
+    #[arg(long, default_value_t = GasPriceConfig::local_node().activity_normal_range_size)]
+    normal_range_size: u16,
+
+    /// Number of blocks in the capped activity range
+    #[arg(long, default_value_t = GasPriceConfig::local_node().activity_capped_range_size)]
+    capped_range_size: u16,
+
+    /// Number of blocks in the decrease activity range
+    #[arg(long, default_value_t = GasPriceConfig::local_node().activity_decrease_range_size)]
+    decrease_range_size: u16,
+
+    /// Block fullness percentage counted as activity
+    #[arg(long, default_value_t = GasPriceConfig::local_node().block_activity_threshold)]
+    block_activity_threshold: u8,
+}
+
+impl GasPriceAlgorithmArgs {
+    fn v1_config(&self, gas_price_factor: NonZeroU64) -> V1AlgorithmConfig {
+        V1AlgorithmConfig {
+            new_exec_gas_price: 0,
+            min_exec_gas_price: self.min_exec_gas_price,
+            exec_gas_price_change_percent: self.exec_gas_price_change_percent,
+            l2_block_fullness_threshold_percent: self.l2_block_fullness_threshold_percent,
+            gas_price_factor,
+            min_da_gas_price: self.min_da_gas_price,
+            max_da_gas_price: self.max_da_gas_price,
+            max_da_gas_price_change_percent: self.max_da_gas_price_change_percent,
+            da_p_component: self.da_p_component,
+            da_d_component: self.da_d_component,
+            normal_range_size: self.normal_range_size,
+            capped_range_size: self.capped_range_size,
+            decrease_range_size: self.decrease_range_size,
+            block_activity_threshold: self.block_activity_threshold,
+            da_poll_interval: None,
+            starting_recorded_height: None,
+            record_metrics: false,
+        }
+    }
+}
+
+impl GasPriceArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the gas price command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::GasPrice,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let result = match self.replay.as_deref() {
+            Some(&[from, to]) => replay(&database_handle, from, to, &self.algorithm)
+                .and_then(|steps| steps.print_json()),
+            _ if self.unrecorded => UnrecordedBlocks::compute(&database_handle)
+                .and_then(|unrecorded| unrecorded.print_json()),
+            _ if !self.set.is_empty() => match self.height {
+                Some(height) => edit_metadata(
+                    &database_handle,
+                    height,
+                    &self.set,
+                    &self.algorithm,
+                    self.yes,
+                ),
+                None => Err(anyhow::anyhow!(
+                    "--set needs --height to select the metadata to edit"
+                )),
+            },
+            _ => GasPriceMetadataEntry::list(&database_handle, self.height)
+                .and_then(|entries| entries.print_json()),
+        };
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// New value of a metadata field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldAssignment {
+    /// name of the field
+    pub field: String,
+    /// new value of the field
+    pub value: String,
+}
+
+impl FromStr for FieldAssignment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (field, value) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected `<FIELD>=<VALUE>`, got `{s}`"))?;
+
+        Ok(Self {
+            field: field.trim().to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+/// Algorithm metadata stored for a height in the `State` column
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GasPriceMetadataEntry {
+    /// height of the block the metadata was computed after
+    pub height: BlockHeight,
+    /// execution gas price of the next block
+    pub exec_gas_price: u64,
+    /// DA gas price of the next block, V1 only
+    pub da_gas_price: Option<u64>,
+    /// the decoded metadata
+    pub metadata: UpdaterMetadata,
+}
+
+impl From<(BlockHeight, UpdaterMetadata)> for GasPriceMetadataEntry {
+    fn from((height, metadata): (BlockHeight, UpdaterMetadata)) -> Self {
+        let (exec_gas_price, da_gas_price) = match &metadata {
+            UpdaterMetadata::V0(v0) => (v0.new_exec_price, None),
+            UpdaterMetadata::V1(v1) => {
+                (v1.new_exec_gas_price(), Some(v1.new_da_gas_price()))
+            }
+        };
+        Self {
+            height,
+            exec_gas_price,
+            da_gas_price,
+            metadata,
+        }
+    }
+}
+
+impl GasPriceMetadataEntry {
+    /// Decode the metadata of every height, or only `height`
+    pub fn list(
+        database_handle: &DatabaseHandle,
+        height: Option<u32>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let gas_price = database_handle.database().gas_price();
+
+        if let Some(height) = height.map(BlockHeight::from) {
+            let metadata = gas_price
+                .storage::<GasPriceMetadata>()
+                .get(&height)?
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Gas price metadata for height {} not found", *height)
+                })?
+                .into_owned();
+            return Ok(vec![(height, metadata).into()]);
+        }
+
+        let entries = gas_price
+            .iter_all::<GasPriceMetadata>(None)
+            .map(|entry| entry.map(Self::from))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+}
+
+/// Blocks whose DA recording cost is still projected
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UnrecordedBlocks {
+    /// latest L2 height recorded on the DA chain
+    pub latest_recorded_height: Option<BlockHeight>,
+    /// total size of the unrecorded blocks in bytes
+    pub total_bytes: u64,
+    /// size in bytes of every unrecorded block
+    pub blocks: BTreeMap<u32, u64>,
+}
+
+impl UnrecordedBlocks {
+    /// Read `LatestRecordedHeight` and every `UnrecordedBlocks` entry
+    pub fn compute(database_handle: &DatabaseHandle) -> anyhow::Result<Self> {
+        let gas_price = database_handle.database().gas_price();
+
+        let latest_recorded_height = gas_price
+            .storage::<RecordedHeights>()
+            .get(&())?
+            .map(|height| height.into_owned());
+
+        let blocks = gas_price
+            .iter_all::<UnrecordedBlocksTable>(None)
+            .map(|entry| entry.map(|(height, bytes)| (*height, bytes)))
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        Ok(Self {
+            latest_recorded_height,
+            total_bytes: blocks
+                .values()
+                .fold(0u64, |total, bytes| total.saturating_add(*bytes)),
+            blocks,
+        })
+    }
+}
+
+/// Prices computed by the replay after a single block
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GasPriceStep {
+    /// height of the replayed block
+    pub height: BlockHeight,
+    /// gas used by the block, approximated from its fees like the service does
+    pub gas_used: u64,
+    /// gas limit of the block
+    pub block_gas_capacity: u64,
+    /// size of the block in bytes
+    pub block_bytes: u64,
+    /// fees collected by the block
+    pub block_fees: u64,
+    /// gas price the block was produced with
+    pub gas_price: u64,
+    /// replayed execution gas price of the next block
+    pub exec_gas_price: u64,
+    /// replayed DA gas price of the next block, V1 only
+    pub da_gas_price: Option<u64>,
+    /// execution gas price stored for the height, if it differs from the replayed one
+    pub stored_exec_gas_price: Option<u64>,
+    /// DA gas price stored for the height, if it differs from the replayed one
+    pub stored_da_gas_price: Option<u64>,
+}
+
+enum Updater {
+    V0(AlgorithmUpdaterV0),
+    V1(AlgorithmUpdaterV1, BTreeMap<u32, u64>),
+}
+
+impl Updater {
+    fn prices(&self) -> (u64, Option<u64>) {
+        match self {
+            Self::V0(updater) => (updater.new_exec_price, None),
+            Self::V1(updater, _) => {
+                let gas_price_factor = updater.gas_price_factor.get();
+                (
+                    updater
+                        .new_scaled_exec_price
+                        .checked_div(gas_price_factor)
+                        .unwrap_or_default(),
+                    Some(
+                        updater
+                            .new_scaled_da_gas_price
+                            .checked_div(gas_price_factor)
+                            .unwrap_or_default(),
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// Replay the L2 block updates of the algorithm over the blocks between `from` and
+/// `to`, starting from the metadata stored for `from - 1`.
+///
+/// DA recording costs aren't stored locally, so the replay only projects them from
+/// the block sizes, like the service does between two recordings.
+pub fn replay(
+    database_handle: &DatabaseHandle,
+    from: u32,
+    to: u32,
+    algorithm: &GasPriceAlgorithmArgs,
+) -> anyhow::Result<Vec<GasPriceStep>> {
+    let gas_price = database_handle.database().gas_price();
+    let on_chain = database_handle.database().on_chain();
+
+    if from == 0 || from > to {
+        anyhow::bail!(
+            "Invalid replay range {from}..={to}, FROM must be positive and not after TO"
+        );
+    }
+
+    let start = BlockHeight::from(from.saturating_sub(1));
+    let metadata = gas_price
+        .storage::<GasPriceMetadata>()
+        .get(&start)?
+        .ok_or_else(|| {
+            anyhow::anyhow!("Gas price metadata for height {} not found", *start)
+        })?
+        .into_owned();
+
+    let mut updater = match metadata {
+        UpdaterMetadata::V0(v0) => Updater::V0(AlgorithmUpdaterV0::new(
+            v0.new_exec_price,
+            v0._min_exec_gas_price,
+            v0._exec_gas_price_change_percent,
+            v0.l2_block_height,
+            v0._l2_block_fullness_threshold_percent,
+        )),
+        UpdaterMetadata::V1(v1) => {
+            let config = algorithm.v1_config(v1.gas_price_factor);
+            Updater::V1(v1_algorithm_from_metadata(v1, &config), BTreeMap::new())
+        }
+    };
+
+    let mut steps = vec![];
+    for height in from..=to {
+        let block = full_block(database_handle, height.into())?;
+        let parameters = on_chain
+            .storage::<ConsensusParametersVersions>()
+            .get(&block.header().consensus_parameters_version())?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Consensus parameters version {} of block {height} not found",
+                    block.header().consensus_parameters_version()
+                )
+            })?;
+
+        let BlockInfo::Block {
+            gas_used,
+            block_gas_capacity,
+            block_bytes,
+            block_fees,
+            gas_price: block_gas_price,
+            ..
+        } = get_block_info(
+            &block,
+            parameters.fee_params().gas_price_factor(),
+            parameters.block_gas_limit(),
+        )?
+        else {
+            anyhow::bail!("Block {height} isn't a regular block");
+        };
+
+        let capacity = NonZeroU64::new(block_gas_capacity)
+            .ok_or_else(|| anyhow::anyhow!("Block {height} has a zero gas limit"))?;
+        match &mut updater {
+            Updater::V0(updater) => {
+                updater.update_l2_block_data(height, gas_used, capacity)?
+            }
+            Updater::V1(updater, unrecorded_blocks) => updater.update_l2_block_data(
+                height,
+                gas_used,
+                capacity,
+                block_bytes,
+                u128::from(block_fees).saturating_mul(1_000_000_000),
+                unrecorded_blocks,
+            )?,
+        }
+
+        let (exec_gas_price, da_gas_price) = updater.prices();
+        let stored = gas_price
+            .storage::<GasPriceMetadata>()
+            .get(&height.into())?
+            .map(|metadata| {
+                GasPriceMetadataEntry::from((height.into(), metadata.into_owned()))
+            });
+
+        steps.push(GasPriceStep {
+            height: height.into(),
+            gas_used,
+            block_gas_capacity,
+            block_bytes,
+            block_fees,
+            gas_price: block_gas_price,
+            exec_gas_price,
+            da_gas_price,
+            stored_exec_gas_price: stored
+                .as_ref()
+                .map(|stored| stored.exec_gas_price)
+                .filter(|stored| *stored != exec_gas_price),
+            stored_da_gas_price: stored
+                .and_then(|stored| stored.da_gas_price)
+                .filter(|stored| Some(*stored) != da_gas_price),
+        });
+    }
+
+    Ok(steps)
+}
+
+fn full_block(
+    database_handle: &DatabaseHandle,
+    height: BlockHeight,
+) -> anyhow::Result<Block<Transaction>> {
+    let on_chain = database_handle.database().on_chain();
+
+    let block = on_chain
+        .storage::<FuelBlocks>()
+        .get(&height)?
+        .ok_or_else(|| anyhow::anyhow!("Block {} not found in FuelBlocks", *height))?
+        .into_owned();
+
+    let transactions = block
+        .transactions()
+        .iter()
+        .map(|tx_id| {
+            on_chain
+                .storage::<Transactions>()
+                .get(tx_id)?
+                .map(|tx| tx.into_owned())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Transaction {tx_id} of block {} not found", *height)
+                })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(block.uncompress(transactions))
+}
+
+/// Apply `assignments` to the metadata stored for `height`, validate the result and
+/// print the changed fields, then write it back only if `confirmed`.
+///
+/// The metadata is stored under its `l2_block_height`, so changing that field moves
+/// the entry to the new height. The result is validated by building the algorithm
+/// updater from it with the `algorithm` configuration.
+pub fn edit_metadata(
+    database_handle: &DatabaseHandle,
+    height: u32,
+    assignments: &[FieldAssignment],
+    algorithm: &GasPriceAlgorithmArgs,
+    confirmed: bool,
+) -> anyhow::Result<()> {
+    let gas_price = database_handle.database().gas_price();
+
+    let height = BlockHeight::from(height);
+    let mut metadata = gas_price
+        .storage::<GasPriceMetadata>()
+        .get(&height)?
+        .ok_or_else(|| {
+            anyhow::anyhow!("Gas price metadata for height {} not found", *height)
+        })?
+        .into_owned();
+
+    let mut changes = vec![];
+    for assignment in assignments {
+        let (before, after) = set_field(&mut metadata, assignment)?;
+        changes.push(format!("{}: {before} -> {after}", assignment.field));
+    }
+
+    let new_height = metadata.l2_block_height();
+    if new_height != height
+        && gas_price
+            .storage::<GasPriceMetadata>()
+            .contains_key(&new_height)?
+    {
+        anyhow::bail!(
+            "Gas price metadata for height {} already exists, refusing to overwrite it",
+            *new_height
+        );
+    }
+
+    for change in &changes {
+        println!("{change}");
+    }
+    if new_height != height {
+        println!("Metadata moves from height {} to {}", *height, *new_height);
+    }
+
+    let next_gas_price = validate_metadata(&metadata, algorithm)?;
+    println!(
+        "Next gas price at height {}: {next_gas_price}",
+        new_height.succ().map_or(*new_height, |height| *height)
+    );
+
+    if let UpdaterMetadata::V1(v1) = &metadata {
+        let unrecorded = UnrecordedBlocks::compute(database_handle)?;
+        if u128::from(unrecorded.total_bytes) != v1.unrecorded_block_bytes {
+            println!(
+                "Warning: unrecorded_block_bytes is {} but the unrecorded blocks add up to {}",
+                v1.unrecorded_block_bytes, unrecorded.total_bytes
+            );
+        }
+    }
+
+    if !confirmed {
+        println!("Pass --yes to write {} change(s)", changes.len());
+        return Ok(());
+    }
+
+    let mut tx = gas_price.read_transaction();
+    if new_height != height {
+        tx.storage_as_mut::<GasPriceMetadata>().remove(&height)?;
+    }
+    tx.storage_as_mut::<GasPriceMetadata>()
+        .insert(&new_height, &metadata)?;
+    database_handle.commit_changes(Database::GasPrice, tx.into_changes())?;
+    println!("Wrote gas price metadata for height {}", *new_height);
+
+    Ok(())
+}
+
+/// Build the algorithm updater from `metadata` and reject the values it would
+/// silently correct or can't represent, returning the next gas price
+fn validate_metadata(
+    metadata: &UpdaterMetadata,
+    algorithm: &GasPriceAlgorithmArgs,
+) -> anyhow::Result<u64> {
+    match metadata {
+        UpdaterMetadata::V0(v0) => {
+            let updater = AlgorithmUpdaterV0::new(
+                v0.new_exec_price,
+                v0._min_exec_gas_price,
+                v0._exec_gas_price_change_percent,
+                v0.l2_block_height,
+                v0._l2_block_fullness_threshold_percent,
+            );
+            if updater.new_exec_price != v0.new_exec_price {
+                anyhow::bail!(
+                    "new_exec_price {} is below min_exec_gas_price {}",
+                    v0.new_exec_price,
+                    v0._min_exec_gas_price
+                );
+            }
+            if v0._l2_block_fullness_threshold_percent > 100 {
+                anyhow::bail!(
+                    "l2_block_fullness_threshold_percent {} is above 100",
+                    v0._l2_block_fullness_threshold_percent
+                );
+            }
+
+            Ok(updater.algorithm().calculate())
+        }
+        UpdaterMetadata::V1(v1) => {
+            let config = algorithm.v1_config(v1.gas_price_factor);
+            let gas_price_factor = v1.gas_price_factor.get();
+            let scaled = |price: u64, name: &str| {
+                price.checked_mul(gas_price_factor).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{name} {price} scaled by gas_price_factor {gas_price_factor} overflows"
+                    )
+                })
+            };
+
+            let min_scaled_exec_price =
+                scaled(config.min_exec_gas_price, "min_exec_gas_price")?;
+            if v1.new_scaled_exec_price < min_scaled_exec_price {
+                anyhow::bail!(
+                    "new_scaled_exec_price {} is below min_exec_gas_price {} scaled by gas_price_factor {gas_price_factor}",
+                    v1.new_scaled_exec_price,
+                    config.min_exec_gas_price
+                );
+            }
+            let min_scaled_da_gas_price =
+                scaled(config.min_da_gas_price, "min_da_gas_price")?;
+            let max_scaled_da_gas_price = scaled(
+                config.max_da_gas_price.max(config.min_da_gas_price),
+                "max_da_gas_price",
+            )?;
+            if !(min_scaled_da_gas_price..=max_scaled_da_gas_price)
+                .contains(&v1.new_scaled_da_gas_price)
+            {
+                anyhow::bail!(
+                    "new_scaled_da_gas_price {} is outside of {min_scaled_da_gas_price}..={max_scaled_da_gas_price}, the DA gas price bounds scaled by gas_price_factor {gas_price_factor}",
+                    v1.new_scaled_da_gas_price
+                );
+            }
+            v1.unrecorded_block_bytes
+                .checked_mul(v1.latest_da_cost_per_byte)
+                .and_then(|projected| {
+                    projected.checked_add(v1.latest_known_total_da_cost)
+                })
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "The projected DA cost of {} unrecorded byte(s) overflows",
+                        v1.unrecorded_block_bytes
+                    )
+                })?;
+
+            let updater = v1_algorithm_from_metadata(v1.clone(), &config);
+            Ok(updater.algorithm().calculate())
+        }
+    }
+}
+
+/// Parse `assignment` into the field of `metadata` it names, returning the previous
+/// and the new value
+fn set_field(
+    metadata: &mut UpdaterMetadata,
+    assignment: &FieldAssignment,
+) -> anyhow::Result<(String, String)> {
+    let value = assignment.value.as_str();
+
+    // the unused V0 fields are prefixed with `_` in the stored metadata
+    match (metadata, assignment.field.trim_start_matches('_')) {
+        (UpdaterMetadata::V0(v0), "new_exec_price") => {
+            replace(&mut v0.new_exec_price, value)
+        }
+        (UpdaterMetadata::V0(v0), "min_exec_gas_price") => {
+            replace(&mut v0._min_exec_gas_price, value)
+        }
+        (UpdaterMetadata::V0(v0), "exec_gas_price_change_percent") => {
+            replace(&mut v0._exec_gas_price_change_percent, value)
+        }
+        (UpdaterMetadata::V0(v0), "l2_block_height") => {
+            replace(&mut v0.l2_block_height, value)
+        }
+        (UpdaterMetadata::V0(v0), "l2_block_fullness_threshold_percent") => {
+            replace(&mut v0._l2_block_fullness_threshold_percent, value)
+        }
+        (UpdaterMetadata::V1(v1), "new_scaled_exec_price") => {
+            replace(&mut v1.new_scaled_exec_price, value)
+        }
+        (UpdaterMetadata::V1(v1), "l2_block_height") => {
+            replace(&mut v1.l2_block_height, value)
+        }
+        (UpdaterMetadata::V1(v1), "new_scaled_da_gas_price") => {
+            replace(&mut v1.new_scaled_da_gas_price, value)
+        }
+        (UpdaterMetadata::V1(v1), "gas_price_factor") => {
+            replace(&mut v1.gas_price_factor, value)
+        }
+        (UpdaterMetadata::V1(v1), "total_da_rewards") => {
+            replace(&mut v1.total_da_rewards, value)
+        }
+        (UpdaterMetadata::V1(v1), "latest_known_total_da_cost") => {
+            replace(&mut v1.latest_known_total_da_cost, value)
+        }
+        (UpdaterMetadata::V1(v1), "last_profit") => replace(&mut v1.last_profit, value),
+        (UpdaterMetadata::V1(v1), "second_to_last_profit") => {
+            replace(&mut v1.second_to_last_profit, value)
+        }
+        (UpdaterMetadata::V1(v1), "latest_da_cost_per_byte") => {
+            replace(&mut v1.latest_da_cost_per_byte, value)
+        }
+        (UpdaterMetadata::V1(v1), "unrecorded_block_bytes") => {
+            replace(&mut v1.unrecorded_block_bytes, value)
+        }
+        (UpdaterMetadata::V0(_), _) => {
+            anyhow::bail!("Unknown V0 metadata field `{}`", assignment.field)
+        }
+        (UpdaterMetadata::V1(_), _) => {
+            anyhow::bail!("Unknown V1 metadata field `{}`", assignment.field)
+        }
+    }
+}
+
+fn replace<T>(field: &mut T, value: &str) -> anyhow::Result<(String, String)>
+where
+    T: FromStr + Display,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let new: T = value
+        .parse()
+        .map_err(|error| anyhow::anyhow!("Invalid value `{value}`: {error}"))?;
+    let old = std::mem::replace(field, new);
+
+    Ok((old.to_string(), field.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use fuel_core_gas_price_service::v1::metadata::V1Metadata;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[clap(flatten)]
+        args: GasPriceArgs,
+    }
+
+    fn open(dir: &tempfile::TempDir) -> DatabaseHandle {
+        DatabaseHandle::try_new_historical(
+            Database::GasPrice,
+            &[Database::OnChain],
+            DatabaseConfig {
+                path: dir.path().to_string_lossy().into_owned(),
+            },
+        )
+        .unwrap()
+    }
+
+    fn run(dir: &tempfile::TempDir, args: &[&str]) -> anyhow::Result<()> {
+        let path = dir.path().to_string_lossy();
+        let args = ["gas-price", "--path", &path, "--height", "1"]
+            .into_iter()
+            .chain(args.iter().copied());
+        Cli::try_parse_from(args)?.args.run()
+    }
+
+    fn stored_metadata(dir: &tempfile::TempDir) -> V1Metadata {
+        let database_handle = open(dir);
+        let metadata = database_handle
+            .database()
+            .gas_price()
+            .storage::<GasPriceMetadata>()
+            .get(&1.into())
+            .unwrap()
+            .unwrap()
+            .into_owned();
+        database_handle.shutdown();
+        match metadata {
+            UpdaterMetadata::V1(v1) => v1,
+            UpdaterMetadata::V0(_) => panic!("expected V1 metadata"),
+        }
+    }
+
+    fn seed_metadata(dir: &tempfile::TempDir) {
+        let database_handle = open(dir);
+        let mut tx = database_handle.database().gas_price().read_transaction();
+        tx.storage_as_mut::<GasPriceMetadata>()
+            .insert(
+                &1.into(),
+                &UpdaterMetadata::V1(V1Metadata {
+                    new_scaled_exec_price: 1000,
+                    l2_block_height: 1,
+                    new_scaled_da_gas_price: 50,
+                    gas_price_factor: NonZeroU64::new(100).unwrap(),
+                    total_da_rewards: 0,
+                    latest_known_total_da_cost: 0,
+                    last_profit: 0,
+                    second_to_last_profit: 0,
+                    latest_da_cost_per_byte: 0,
+                    unrecorded_block_bytes: 0,
+                }),
+            )
+            .unwrap();
+        database_handle
+            .commit_changes(Database::GasPrice, tx.into_changes())
+            .unwrap();
+        database_handle.shutdown();
+    }
+
+    #[test]
+    fn set_writes_the_metadata_only_with_yes() {
+        let dir = tempfile::tempdir().unwrap();
+        seed_metadata(&dir);
+
+        run(&dir, &["--set", "new_scaled_exec_price=2000"]).unwrap();
+        assert_eq!(stored_metadata(&dir).new_scaled_exec_price, 1000);
+
+        run(&dir, &["--set", "new_scaled_exec_price=2000", "--yes"]).unwrap();
+        assert_eq!(stored_metadata(&dir).new_scaled_exec_price, 2000);
+    }
+
+    #[test]
+    fn set_refuses_values_outside_of_the_algorithm_bounds() {
+        let dir = tempfile::tempdir().unwrap();
+        seed_metadata(&dir);
+
+        // the default DA gas price bounds are 0..=1, scaled by 100
+        let error =
+            run(&dir, &["--set", "new_scaled_da_gas_price=500", "--yes"]).unwrap_err();
+        assert!(error.to_string().contains("outside of 0..=100"));
+        assert_eq!(stored_metadata(&dir).new_scaled_da_gas_price, 50);
+    }
+}
@@ -0,0 +1,482 @@
+//! Inspect and reset the per-table progress of a genesis import
+
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    str::FromStr,
+};
+
+use fuel_core::database::{
+    Database as FuelDatabase,
+    database_description::{
+        DatabaseDescription,
+        off_chain::OffChain,
+        on_chain::OnChain,
+    },
+    genesis_progress::GenesisMetadata,
+};
+use fuel_core_storage::{
+    Error as StorageError,
+    StorageAsMut,
+    StorageMutate,
+    iter::{
+        IterDirection,
+        IterableStore,
+        IteratorOverTable,
+    },
+    kv_store::{
+        KeyValueMutate,
+        StorageColumn,
+    },
+    tables::FuelBlocks,
+    transactional::{
+        Changes,
+        ReadTransaction,
+        StorageTransaction,
+    },
+};
+
+use crate::{
+    columns::{
+        OffChainColumn,
+        OnchainColumn,
+    },
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+};
+
+/// Genesis command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct GenesisArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Number of groups the snapshot has for a table, as `<KEY>=<COUNT>`,
+    /// used to tell whether the table is complete
+    #[arg(long, value_name = "KEY=COUNT")]
+    groups: Vec<GroupCount>,
+
+    /// Reset the progress of this table so it is imported again from the first group
+    #[arg(long, value_name = "KEY")]
+    reset: Vec<String>,
+
+    /// Actually write the resets instead of only printing them
+    #[arg(long, requires = "reset")]
+    yes: bool,
+}
+
+/// Number of snapshot groups of a table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupCount {
+    /// progress key of the table
+    pub key: String,
+    /// number of groups in the snapshot
+    pub count: usize,
+}
+
+impl FromStr for GroupCount {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, count) = s
+            .rsplit_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected `<KEY>=<COUNT>`, got `{s}`"))?;
+
+        Ok(Self {
+            key: key.to_string(),
+            count: count.parse()?,
+        })
+    }
+}
+
+impl GenesisArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the genesis command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OnChain,
+            &[Database::OffChain],
+            self.database_config.clone(),
+        )?;
+
+        let result = GenesisProgress::compute(&database_handle).and_then(|progress| {
+            progress.print(&self.groups);
+            if self.reset.is_empty() {
+                Ok(())
+            } else {
+                progress.reset(&database_handle, &self.reset, self.yes)
+            }
+        });
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// Genesis import progress of both databases
+#[derive(Debug, Clone, Default)]
+pub struct GenesisProgress {
+    /// whether the genesis block was committed, which ends the import
+    pub finished: bool,
+    /// index of the last imported group per on-chain table
+    pub on_chain: BTreeMap<String, usize>,
+    /// index of the last imported group per off-chain table
+    pub off_chain: BTreeMap<String, usize>,
+}
+
+impl GenesisProgress {
+    /// Decode the on-chain and off-chain `GenesisMetadata`
+    pub fn compute(database_handle: &DatabaseHandle) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let finished = on_chain
+            .iter_all_keys::<FuelBlocks>(Some(IterDirection::Reverse))
+            .next()
+            .transpose()?
+            .is_some();
+
+        Ok(Self {
+            finished,
+            on_chain: on_chain
+                .iter_all::<GenesisMetadata<OnChain>>(None)
+                .collect::<Result<_, _>>()?,
+            off_chain: off_chain
+                .iter_all::<GenesisMetadata<OffChain>>(None)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Print the progress of every table, comparing it with the known group counts
+    pub fn print(&self, groups: &[GroupCount]) {
+        if self.finished && self.on_chain.is_empty() && self.off_chain.is_empty() {
+            println!("Genesis import finished");
+            return;
+        }
+
+        for (database, progress) in
+            [("on-chain", &self.on_chain), ("off-chain", &self.off_chain)]
+        {
+            for (key, last_group) in progress {
+                let imported = last_group.saturating_add(1);
+                let state = match groups.iter().find(|groups| groups.key == *key) {
+                    Some(groups) if imported >= groups.count => "complete".to_string(),
+                    Some(groups) => format!("{imported}/{} groups", groups.count),
+                    None => format!("{imported} group(s)"),
+                };
+                println!("{database} `{key}`: {state}");
+            }
+        }
+
+        if self.finished {
+            println!("Genesis block is committed, but progress entries are left over");
+        } else {
+            println!("Genesis import in progress");
+        }
+    }
+
+    /// Remove the progress of the `keys` tables together with the rows their import
+    /// wrote, or only print what would be removed unless `confirmed`, so an
+    /// interrupted import starts the tables again from their first group.
+    ///
+    /// The rows are cleared by column, so every other table with stored progress that
+    /// wrote into the same columns must be reset too. The two databases can't be
+    /// written atomically, so all `keys` must belong to the same database.
+    pub fn reset(
+        &self,
+        database_handle: &DatabaseHandle,
+        keys: &[String],
+        confirmed: bool,
+    ) -> anyhow::Result<()> {
+        if self.finished {
+            anyhow::bail!(
+                "Genesis block is already committed, resetting the import progress has no effect"
+            );
+        }
+
+        if let Some(key) = keys.iter().find(|key| {
+            !self.on_chain.contains_key(*key) && !self.off_chain.contains_key(*key)
+        }) {
+            anyhow::bail!("No genesis progress stored for `{key}`");
+        }
+
+        let on_chain = keys.iter().any(|key| self.on_chain.contains_key(key));
+        let off_chain = keys.iter().any(|key| self.off_chain.contains_key(key));
+        if on_chain && off_chain {
+            anyhow::bail!(
+                "The on-chain and off-chain progress can't be reset atomically, reset the tables of each database in a separate run"
+            );
+        }
+
+        let columns = keys
+            .iter()
+            .flat_map(|key| written_columns(key))
+            .collect::<BTreeSet<_>>();
+        let progress = if on_chain {
+            &self.on_chain
+        } else {
+            &self.off_chain
+        };
+        for other in progress.keys().filter(|other| !keys.contains(other)) {
+            if let Some(column) = written_columns(other)
+                .into_iter()
+                .find(|column| columns.contains(column))
+            {
+                anyhow::bail!(
+                    "`{other}` also wrote into `{column}`, reset it in the same run"
+                );
+            }
+        }
+
+        let (changes, rows) = if on_chain {
+            reset_changes::<OnchainColumn, _>(
+                database_handle.database().on_chain(),
+                keys,
+                &columns,
+            )?
+        } else {
+            reset_changes::<OffChainColumn, _>(
+                database_handle.database().off_chain(),
+                keys,
+                &columns,
+            )?
+        };
+
+        for (column, rows) in &rows {
+            println!("`{column}`: {rows} row(s) to remove");
+        }
+        if !confirmed {
+            for key in keys {
+                println!("Would reset `{key}`");
+            }
+            println!("Pass --yes to reset {} table(s)", keys.len());
+            return Ok(());
+        }
+
+        database_handle.commit_changes(
+            if on_chain {
+                Database::OnChain
+            } else {
+                Database::OffChain
+            },
+            changes,
+        )?;
+        println!(
+            "Reset {} table(s) and cleared {} column(s)",
+            keys.len(),
+            rows.len()
+        );
+
+        Ok(())
+    }
+}
+
+/// Columns the import of the table with progress `key` writes, the written table
+/// followed by the columns fuel-core updates along with it
+fn written_columns(key: &str) -> Vec<&str> {
+    let written = key.rsplit_once(" -> ").map_or(key, |(_, written)| written);
+    let updated_along: &[&str] = match written {
+        "ContractsState" => &["ContractsStateMerkleData", "ContractsStateMerkleMetadata"],
+        "ContractsAssets" => {
+            &["ContractsAssetsMerkleData", "ContractsAssetsMerkleMetadata"]
+        }
+        "OwnedCoins" => &["CoinBalances", "CoinsToSpend"],
+        "OwnedMessageIds" => &["MessageBalances", "CoinsToSpend"],
+        _ => &[],
+    };
+
+    core::iter::once(written)
+        .chain(updated_along.iter().copied())
+        .collect()
+}
+
+/// Changes removing the progress of `keys` and every row of `columns` from
+/// `database`, with the number of removed rows per column
+fn reset_changes<'a, ColumnName, Description>(
+    database: &'a FuelDatabase<Description>,
+    keys: &[String],
+    columns: &BTreeSet<&str>,
+) -> anyhow::Result<(Changes, BTreeMap<String, usize>)>
+where
+    ColumnName: enum_iterator::Sequence + Into<Description::Column>,
+    Description: DatabaseDescription,
+    StorageTransaction<&'a FuelDatabase<Description>>:
+        StorageMutate<GenesisMetadata<Description>, Error = StorageError>,
+{
+    let mut tx = database.read_transaction();
+    for key in keys {
+        tx.storage_as_mut::<GenesisMetadata<Description>>()
+            .remove(key.as_str())?;
+    }
+
+    let mut rows = BTreeMap::new();
+    for name in columns {
+        let column = enum_iterator::all::<ColumnName>()
+            .map(Into::<Description::Column>::into)
+            .find(|column| column.name() == *name)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Unknown {} column `{name}`", Description::name())
+            })?;
+
+        let stored = database
+            .iter_store_keys(column, None, None, IterDirection::Forward)
+            .collect::<Result<Vec<_>, _>>()?;
+        for key in &stored {
+            tx.delete(key, column)?;
+        }
+        rows.insert(name.to_string(), stored.len());
+    }
+
+    Ok((tx.into_changes(), rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_core::types::{
+        entities::coins::coin::CompressedCoin,
+        fuel_tx::{
+            ContractId,
+            UtxoId,
+        },
+    };
+    use fuel_core_storage::{
+        StorageAsRef,
+        tables::{
+            Coins,
+            ContractsRawCode,
+        },
+    };
+
+    use super::*;
+
+    fn open(dir: &tempfile::TempDir) -> DatabaseHandle {
+        DatabaseHandle::try_new_historical(
+            Database::OnChain,
+            &[Database::OffChain],
+            DatabaseConfig {
+                path: dir.path().to_string_lossy().into_owned(),
+            },
+        )
+        .unwrap()
+    }
+
+    fn coins(database_handle: &DatabaseHandle) -> usize {
+        database_handle
+            .database()
+            .on_chain()
+            .iter_all_keys::<Coins>(None)
+            .count()
+    }
+
+    /// Progress and rows left behind by an import interrupted after the second group
+    /// of coins and the first group of contract codes
+    fn interrupted_import(dir: &tempfile::TempDir) {
+        let database_handle = open(dir);
+        let mut tx = database_handle.database().on_chain().read_transaction();
+        tx.storage_as_mut::<GenesisMetadata<OnChain>>()
+            .insert("Coins -> Coins", &1)
+            .unwrap();
+        for output_index in 0..4 {
+            tx.storage_as_mut::<Coins>()
+                .insert(
+                    &UtxoId::new(Default::default(), output_index),
+                    &CompressedCoin::default(),
+                )
+                .unwrap();
+        }
+        tx.storage_as_mut::<GenesisMetadata<OnChain>>()
+            .insert("ContractsRawCode -> ContractsRawCode", &0)
+            .unwrap();
+        tx.storage_as_mut::<ContractsRawCode>()
+            .insert(&ContractId::zeroed(), &[1; 4])
+            .unwrap();
+        database_handle
+            .commit_changes(Database::OnChain, tx.into_changes())
+            .unwrap();
+        database_handle.shutdown();
+    }
+
+    #[test]
+    fn reset_clears_the_progress_and_the_written_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        interrupted_import(&dir);
+        let database_handle = open(&dir);
+        let keys = ["Coins -> Coins".to_string()];
+
+        let progress = GenesisProgress::compute(&database_handle).unwrap();
+        progress.reset(&database_handle, &keys, false).unwrap();
+        let unchanged = GenesisProgress::compute(&database_handle).unwrap();
+        assert_eq!(unchanged.on_chain.get("Coins -> Coins"), Some(&1));
+        assert_eq!(coins(&database_handle), 4);
+
+        progress.reset(&database_handle, &keys, true).unwrap();
+        let reset = GenesisProgress::compute(&database_handle).unwrap();
+        assert!(!reset.on_chain.contains_key("Coins -> Coins"));
+        assert_eq!(
+            reset.on_chain.get("ContractsRawCode -> ContractsRawCode"),
+            Some(&0)
+        );
+        assert_eq!(coins(&database_handle), 0);
+        assert!(
+            database_handle
+                .database()
+                .on_chain()
+                .storage::<ContractsRawCode>()
+                .contains_key(&ContractId::zeroed())
+                .unwrap()
+        );
+
+        database_handle.shutdown();
+    }
+
+    #[test]
+    fn reset_refuses_tables_sharing_a_column() {
+        let dir = tempfile::tempdir().unwrap();
+        let database_handle = open(&dir);
+        let mut tx = database_handle.database().off_chain().read_transaction();
+        for key in ["Coins -> OwnedCoins", "Messages -> OwnedMessageIds"] {
+            tx.storage_as_mut::<GenesisMetadata<OffChain>>()
+                .insert(key, &0)
+                .unwrap();
+        }
+        database_handle
+            .commit_changes(Database::OffChain, tx.into_changes())
+            .unwrap();
+
+        let progress = GenesisProgress::compute(&database_handle).unwrap();
+        assert!(
+            progress
+                .reset(&database_handle, &["Coins -> OwnedCoins".to_string()], true)
+                .is_err()
+        );
+        progress
+            .reset(
+                &database_handle,
+                &[
+                    "Coins -> OwnedCoins".to_string(),
+                    "Messages -> OwnedMessageIds".to_string(),
+                ],
+                true,
+            )
+            .unwrap();
+        assert!(
+            GenesisProgress::compute(&database_handle)
+                .unwrap()
+                .off_chain
+                .is_empty()
+        );
+
+        database_handle.shutdown();
+    }
+}
@@ -0,0 +1,299 @@
+//! Report the coins, messages, balances and transaction history of an owner
+
+use core::str::FromStr;
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::{
+        balances::{
+            CoinBalances,
+            MessageBalance,
+            MessageBalances,
+        },
+        coins::OwnedCoins,
+        messages::OwnedMessageIds,
+        transactions::{
+            OwnedTransactionIndexKey,
+            OwnedTransactions,
+            TransactionIndex,
+        },
+    },
+    types::{
+        entities::{
+            coins::coin::Coin,
+            relayer::message::Message,
+        },
+        fuel_tx::{
+            TxId,
+            UtxoId,
+        },
+        fuel_types::{
+            Address,
+            AssetId,
+            BlockHeight,
+            Nonce,
+        },
+    },
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::{
+        IterDirection,
+        IteratorOverTable,
+    },
+    tables::{
+        Coins,
+        Messages,
+    },
+};
+
+use crate::{
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+};
+
+/// Owner report command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct OwnerArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Address of the owner
+    owner: Address,
+
+    /// Number of transactions to return
+    #[arg(long, default_value_t = 10)]
+    limit: usize,
+
+    /// Direction to page through the transaction history in
+    #[arg(long, value_enum, default_value_t = PageDirection::Backward)]
+    direction: PageDirection,
+
+    /// Cursor to continue the transaction history after, as `<height>:<tx_idx>`
+    #[arg(long)]
+    after: Option<TransactionCursor>,
+}
+
+impl OwnerArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the owner report
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let report = OwnerReport::compute(
+            &database_handle,
+            self.owner,
+            self.limit,
+            self.direction,
+            self.after,
+        );
+        database_handle.shutdown();
+
+        report?.print_json()
+    }
+}
+
+/// Direction of the transaction history paging
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageDirection {
+    /// oldest transactions first
+    Forward,
+    /// most recent transactions first
+    Backward,
+}
+
+impl From<PageDirection> for IterDirection {
+    fn from(direction: PageDirection) -> Self {
+        match direction {
+            PageDirection::Forward => IterDirection::Forward,
+            PageDirection::Backward => IterDirection::Reverse,
+        }
+    }
+}
+
+/// Position in the transaction history of an owner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionCursor {
+    /// height of the block
+    pub block_height: BlockHeight,
+    /// index of the transaction in the block
+    pub tx_idx: TransactionIndex,
+}
+
+impl FromStr for TransactionCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (block_height, tx_idx) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Expected `<height>:<tx_idx>`, got `{s}`"))?;
+
+        Ok(Self {
+            block_height: block_height.parse::<u32>()?.into(),
+            tx_idx: tx_idx.parse()?,
+        })
+    }
+}
+
+impl core::fmt::Display for TransactionCursor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}", *self.block_height, self.tx_idx)
+    }
+}
+
+/// Coin balance of a single asset
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AssetBalance {
+    /// id of the asset
+    pub asset_id: AssetId,
+    /// stored balance
+    pub amount: u128,
+}
+
+/// Transaction in the history of an owner
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OwnedTransaction {
+    /// height of the block
+    pub block_height: BlockHeight,
+    /// index of the transaction in the block
+    pub tx_idx: TransactionIndex,
+    /// id of the transaction
+    pub tx_id: TxId,
+}
+
+/// Everything the off-chain indexes know about an owner
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OwnerReport {
+    /// address of the owner
+    pub owner: Address,
+    /// coins indexed in `OwnedCoins` and present in `Coins`
+    pub coins: Vec<Coin>,
+    /// coins indexed in `OwnedCoins` but missing from `Coins`
+    pub missing_coins: Vec<UtxoId>,
+    /// messages indexed in `OwnedMessageIds` and present in `Messages`
+    pub messages: Vec<Message>,
+    /// messages indexed in `OwnedMessageIds` but missing from `Messages`
+    pub missing_messages: Vec<Nonce>,
+    /// stored coin balances per asset
+    pub coin_balances: Vec<AssetBalance>,
+    /// stored message balance
+    pub message_balance: Option<MessageBalance>,
+    /// page of the transaction history
+    pub transactions: Vec<OwnedTransaction>,
+    /// cursor to request the next page with, if there may be more transactions
+    pub next_cursor: Option<String>,
+}
+
+impl OwnerReport {
+    /// Join the off-chain owner indexes with the on-chain `Coins` and `Messages`
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        owner: Address,
+        limit: usize,
+        direction: PageDirection,
+        after: Option<TransactionCursor>,
+    ) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let mut coins = vec![];
+        let mut missing_coins = vec![];
+        for entry in off_chain.iter_all_by_prefix_keys::<OwnedCoins, _>(Some(owner)) {
+            let key = entry?;
+            let (_, utxo_id) = key.split_at(Address::LEN);
+            let (tx_id, output_index) = utxo_id.split_at(TxId::LEN);
+            let utxo_id = UtxoId::new(
+                TxId::try_from(tx_id)?,
+                u16::from_be_bytes(output_index.try_into()?),
+            );
+            match on_chain.storage::<Coins>().get(&utxo_id)? {
+                Some(coin) => coins.push(coin.into_owned().uncompress(utxo_id)),
+                None => missing_coins.push(utxo_id),
+            }
+        }
+
+        let mut messages = vec![];
+        let mut missing_messages = vec![];
+        for entry in off_chain.iter_all_by_prefix_keys::<OwnedMessageIds, _>(Some(owner))
+        {
+            let nonce = *entry?.nonce();
+            match on_chain.storage::<Messages>().get(&nonce)? {
+                Some(message) => messages.push(message.into_owned()),
+                None => missing_messages.push(nonce),
+            }
+        }
+
+        let coin_balances = off_chain
+            .iter_all_by_prefix::<CoinBalances, _>(Some(owner))
+            .map(|entry| {
+                entry.map(|(key, amount)| AssetBalance {
+                    asset_id: *key.asset_id(),
+                    amount,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let message_balance = off_chain
+            .storage::<MessageBalances>()
+            .get(&owner)?
+            .map(|balance| balance.into_owned());
+
+        let start = after.map(|cursor| {
+            OwnedTransactionIndexKey::new(&owner, cursor.block_height, cursor.tx_idx)
+        });
+        let mut transactions = vec![];
+        for entry in off_chain.iter_all_filtered::<OwnedTransactions, _>(
+            Some(owner),
+            start.as_ref(),
+            Some(direction.into()),
+        ) {
+            if transactions.len() >= limit {
+                break;
+            }
+            let (key, tx_id) = entry?;
+            if Some(&key) == start.as_ref() {
+                continue;
+            }
+            transactions.push(OwnedTransaction {
+                block_height: key.block_height,
+                tx_idx: key.tx_idx,
+                tx_id,
+            });
+        }
+
+        let next_cursor = (limit > 0 && transactions.len() == limit)
+            .then(|| transactions.last())
+            .flatten()
+            .map(|tx| {
+                TransactionCursor {
+                    block_height: tx.block_height,
+                    tx_idx: tx.tx_idx,
+                }
+                .to_string()
+            });
+
+        Ok(Self {
+            owner,
+            coins,
+            missing_coins,
+            messages,
+            missing_messages,
+            coin_balances,
+            message_balance,
+            transactions,
+            next_cursor,
+        })
+    }
+}
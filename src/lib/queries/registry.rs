@@ -0,0 +1,517 @@
+//! Resolve compression registry keys and values, and verify the `RegistryIndex`
+
+use fuel_core::{
+    database::{
+        Database as FuelDatabase,
+        database_description::compression::CompressionDatabase,
+    },
+    types::{
+        fuel_compression::RegistryKey,
+        fuel_tx::{
+            Address,
+            AssetId,
+            ContractId,
+            ScriptCode,
+            input::PredicateCode,
+        },
+    },
+};
+use fuel_core_compression_service::storage::{
+    self,
+    evictor_cache::MetadataKey,
+    registry_index::ReverseKey,
+    timestamps::TimestampKeyspace,
+};
+use fuel_core_storage::{
+    Error as StorageError,
+    Mappable,
+    StorageAsRef,
+    StorageInspect,
+    iter::{
+        IterableTable,
+        IteratorOverTable,
+    },
+};
+
+use crate::{
+    cli::hex_string_to_bytes,
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::{
+        PrintJsonExt,
+        hex_string,
+    },
+};
+
+/// Registry command configuration
+#[derive(clap::Args, Debug, Clone)]
+#[command(group(
+    clap::ArgGroup::new("mode").required(true).args(["key", "value", "verify"])
+))]
+pub struct RegistryArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Registry table to look up, or to restrict the verification to
+    #[arg(long, value_enum)]
+    table: Option<RegistryTable>,
+
+    /// Resolve this registry key to its value
+    #[arg(long, requires = "table", conflicts_with = "value")]
+    key: Option<u32>,
+
+    /// Resolve this value to its registry key, as hex, the full bytecode for the
+    /// code tables
+    #[arg(long, requires = "table")]
+    value: Option<String>,
+
+    /// Verify that the registry tables and `RegistryIndex` agree
+    #[arg(long, conflicts_with_all = ["key", "value"])]
+    verify: bool,
+}
+
+impl RegistryArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the registry command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle =
+            DatabaseHandle::try_new(Database::Compression, self.database_config.clone())?;
+
+        let result = match (self.table, self.key, &self.value) {
+            (Some(table), Some(key), _) => {
+                RegistryEntry::by_key(&database_handle, table, key)
+                    .and_then(|entry| entry.print_json())
+            }
+            (Some(table), None, Some(value)) => {
+                RegistryEntry::by_value(&database_handle, table, value)
+                    .and_then(|entry| entry.print_json())
+            }
+            _ => RegistryReport::compute(&database_handle, self.table)
+                .map(|report| report.print()),
+        };
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// A registry table of the compression database
+#[derive(
+    clap::ValueEnum,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    strum::Display,
+    enum_iterator::Sequence,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum RegistryTable {
+    /// `Address` table
+    Address,
+    /// `AssetId` table
+    AssetId,
+    /// `ContractId` table
+    ContractId,
+    /// `ScriptCode` table
+    ScriptCode,
+    /// `PredicateCode` table
+    PredicateCode,
+}
+
+impl RegistryTable {
+    /// Key of the table in `EvictorCache`
+    pub fn metadata_key(self) -> MetadataKey {
+        match self {
+            Self::Address => MetadataKey::Address,
+            Self::AssetId => MetadataKey::AssetId,
+            Self::ContractId => MetadataKey::ContractId,
+            Self::ScriptCode => MetadataKey::ScriptCode,
+            Self::PredicateCode => MetadataKey::PredicateCode,
+        }
+    }
+
+    /// Keyspace of the table in `Timestamps`
+    pub fn timestamp_keyspace(self) -> TimestampKeyspace {
+        match self {
+            Self::Address => TimestampKeyspace::Address,
+            Self::AssetId => TimestampKeyspace::AssetId,
+            Self::ContractId => TimestampKeyspace::ContractId,
+            Self::ScriptCode => TimestampKeyspace::ScriptCode,
+            Self::PredicateCode => TimestampKeyspace::PredicateCode,
+        }
+    }
+}
+
+impl From<&ReverseKey> for RegistryTable {
+    fn from(reverse_key: &ReverseKey) -> Self {
+        match reverse_key {
+            ReverseKey::Address(_) => Self::Address,
+            ReverseKey::AssetId(_) => Self::AssetId,
+            ReverseKey::ContractId(_) => Self::ContractId,
+            ReverseKey::ScriptCode(_) => Self::ScriptCode,
+            ReverseKey::PredicateCode(_) => Self::PredicateCode,
+        }
+    }
+}
+
+/// A registry entry with its reverse index
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RegistryEntry {
+    /// registry table of the entry
+    pub table: RegistryTable,
+    /// registry key of the entry
+    pub key: u32,
+    /// value of the entry, as hex
+    pub value: String,
+    /// key of the value in `RegistryIndex`, the code hash for the code tables
+    pub reverse_key: ReverseKey,
+    /// registry key `RegistryIndex` maps the value to
+    pub indexed_key: Option<u32>,
+}
+
+impl RegistryEntry {
+    /// Resolve `key` in `table` to its value
+    pub fn by_key(
+        database_handle: &DatabaseHandle,
+        table: RegistryTable,
+        key: u32,
+    ) -> anyhow::Result<Self> {
+        let registry_key = RegistryKey::try_from(key).map_err(anyhow::Error::msg)?;
+
+        Self::read(database_handle, table, &registry_key)?
+            .ok_or_else(|| anyhow::anyhow!("No {table} registry entry for key {key}"))
+    }
+
+    /// Resolve the hex `value` to its current registry key in `table`
+    pub fn by_value(
+        database_handle: &DatabaseHandle,
+        table: RegistryTable,
+        value: &str,
+    ) -> anyhow::Result<Self> {
+        let compression = database_handle.database().compression();
+        let bytes = hex_string_to_bytes(value)?;
+
+        let reverse_key = match table {
+            RegistryTable::Address => ReverseKey::from(&Address::try_from(&bytes[..])?),
+            RegistryTable::AssetId => ReverseKey::from(&AssetId::try_from(&bytes[..])?),
+            RegistryTable::ContractId => {
+                ReverseKey::from(&ContractId::try_from(&bytes[..])?)
+            }
+            RegistryTable::ScriptCode => ReverseKey::from(&ScriptCode::from(&bytes[..])),
+            RegistryTable::PredicateCode => {
+                ReverseKey::from(&PredicateCode::from(&bytes[..]))
+            }
+        };
+
+        let key = compression
+            .storage::<storage::RegistryIndex>()
+            .get(&reverse_key)?
+            .ok_or_else(|| {
+                anyhow::anyhow!("Value is not indexed in the {table} registry")
+            })?
+            .into_owned();
+
+        Self::read(database_handle, table, &key)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Value is indexed under key {} in the {table} registry, but the entry is missing",
+                key.as_u32()
+            )
+        })
+    }
+
+    /// Read the entry at `key` of `table`, if any
+    pub(crate) fn read(
+        database_handle: &DatabaseHandle,
+        table: RegistryTable,
+        key: &RegistryKey,
+    ) -> anyhow::Result<Option<Self>> {
+        let compression = database_handle.database().compression();
+
+        match table {
+            RegistryTable::Address => {
+                read_entry::<storage::Address>(compression, table, key)
+            }
+            RegistryTable::AssetId => {
+                read_entry::<storage::AssetId>(compression, table, key)
+            }
+            RegistryTable::ContractId => {
+                read_entry::<storage::ContractId>(compression, table, key)
+            }
+            RegistryTable::ScriptCode => {
+                read_entry::<storage::ScriptCode>(compression, table, key)
+            }
+            RegistryTable::PredicateCode => {
+                read_entry::<storage::PredicateCode>(compression, table, key)
+            }
+        }
+    }
+}
+
+/// Read the entry at `key` of the registry table `T` and its reverse index
+fn read_entry<T>(
+    compression: &FuelDatabase<CompressionDatabase>,
+    table: RegistryTable,
+    key: &RegistryKey,
+) -> anyhow::Result<Option<RegistryEntry>>
+where
+    T: Mappable<Key = RegistryKey>,
+    T::OwnedValue: AsRef<[u8]>,
+    for<'a> ReverseKey: From<&'a T::OwnedValue>,
+    FuelDatabase<CompressionDatabase>: StorageInspect<T, Error = StorageError>,
+{
+    let Some(value) = compression.storage::<T>().get(key)? else {
+        return Ok(None);
+    };
+    let value = value.into_owned();
+    let reverse_key = ReverseKey::from(&value);
+    let indexed_key = compression
+        .storage::<storage::RegistryIndex>()
+        .get(&reverse_key)?
+        .map(|key| key.as_u32());
+
+    Ok(Some(RegistryEntry {
+        table,
+        key: key.as_u32(),
+        value: hex_string(value.as_ref()),
+        reverse_key,
+        indexed_key,
+    }))
+}
+
+/// A disagreement between a registry table and `RegistryIndex`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryViolation {
+    /// The value of the entry isn't indexed
+    Unindexed {
+        /// registry table of the entry
+        table: RegistryTable,
+        /// registry key of the entry
+        key: u32,
+    },
+    /// The value of the entry is indexed under another key
+    WrongIndex {
+        /// registry table of the entry
+        table: RegistryTable,
+        /// registry key of the entry
+        key: u32,
+        /// registry key stored in `RegistryIndex`
+        indexed_key: u32,
+    },
+    /// The index points to a key without entry
+    Dangling {
+        /// registry table of the index
+        table: RegistryTable,
+        /// registry key stored in `RegistryIndex`
+        key: u32,
+    },
+    /// The index points to an entry holding another value
+    Stale {
+        /// registry table of the index
+        table: RegistryTable,
+        /// registry key stored in `RegistryIndex`
+        key: u32,
+    },
+}
+
+impl core::fmt::Display for RegistryViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unindexed { table, key } => {
+                write!(f, "value is not indexed: table={table} key={key}")
+            }
+            Self::WrongIndex {
+                table,
+                key,
+                indexed_key,
+            } => write!(
+                f,
+                "value is indexed under another key: table={table} key={key} indexed_key={indexed_key}"
+            ),
+            Self::Dangling { table, key } => {
+                write!(
+                    f,
+                    "index points to a missing entry: table={table} key={key}"
+                )
+            }
+            Self::Stale { table, key } => {
+                write!(
+                    f,
+                    "index points to an entry with another value: table={table} key={key}"
+                )
+            }
+        }
+    }
+}
+
+/// Result of verifying the registry tables against `RegistryIndex`
+#[derive(Debug, Clone, Default)]
+pub struct RegistryReport {
+    /// number of checked registry entries
+    pub entries: usize,
+    /// number of checked `RegistryIndex` entries
+    pub indexes: usize,
+    /// all problems found
+    pub violations: Vec<RegistryViolation>,
+}
+
+impl RegistryReport {
+    /// Check that every entry of the registry tables is indexed under its own key,
+    /// and that every `RegistryIndex` entry points to an entry, only for `table` if
+    /// set.
+    ///
+    /// A value indexed under another key is reported once, from the entry side.
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        table: Option<RegistryTable>,
+    ) -> anyhow::Result<Self> {
+        let compression = database_handle.database().compression();
+        let mut report = Self::default();
+
+        for current in enum_iterator::all::<RegistryTable>() {
+            if table.is_some_and(|table| table != current) {
+                continue;
+            }
+            match current {
+                RegistryTable::Address => {
+                    report.check_entries::<storage::Address>(compression, current)?
+                }
+                RegistryTable::AssetId => {
+                    report.check_entries::<storage::AssetId>(compression, current)?
+                }
+                RegistryTable::ContractId => {
+                    report.check_entries::<storage::ContractId>(compression, current)?
+                }
+                RegistryTable::ScriptCode => {
+                    report.check_entries::<storage::ScriptCode>(compression, current)?
+                }
+                RegistryTable::PredicateCode => report
+                    .check_entries::<storage::PredicateCode>(compression, current)?,
+            }
+        }
+
+        for entry in compression.iter_all::<storage::RegistryIndex>(None) {
+            let (reverse_key, key) = entry?;
+            let current = RegistryTable::from(&reverse_key);
+            if table.is_some_and(|table| table != current) {
+                continue;
+            }
+            report.indexes = report.indexes.saturating_add(1);
+
+            let matches = match current {
+                RegistryTable::Address => {
+                    index_matches::<storage::Address>(compression, &key, &reverse_key)?
+                }
+                RegistryTable::AssetId => {
+                    index_matches::<storage::AssetId>(compression, &key, &reverse_key)?
+                }
+                RegistryTable::ContractId => {
+                    index_matches::<storage::ContractId>(compression, &key, &reverse_key)?
+                }
+                RegistryTable::ScriptCode => {
+                    index_matches::<storage::ScriptCode>(compression, &key, &reverse_key)?
+                }
+                RegistryTable::PredicateCode => index_matches::<storage::PredicateCode>(
+                    compression,
+                    &key,
+                    &reverse_key,
+                )?,
+            };
+            match matches {
+                Some(true) => {}
+                Some(false) => report.violations.push(RegistryViolation::Stale {
+                    table: current,
+                    key: key.as_u32(),
+                }),
+                None => report.violations.push(RegistryViolation::Dangling {
+                    table: current,
+                    key: key.as_u32(),
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Check that every entry of the registry table `T` is indexed under its own key
+    fn check_entries<T>(
+        &mut self,
+        compression: &FuelDatabase<CompressionDatabase>,
+        table: RegistryTable,
+    ) -> anyhow::Result<()>
+    where
+        T: Mappable<OwnedKey = RegistryKey>,
+        for<'a> ReverseKey: From<&'a T::OwnedValue>,
+        FuelDatabase<CompressionDatabase>: IterableTable<T>,
+    {
+        for entry in compression.iter_all::<T>(None) {
+            let (key, value) = entry?;
+            self.entries = self.entries.saturating_add(1);
+
+            let indexed_key = compression
+                .storage::<storage::RegistryIndex>()
+                .get(&ReverseKey::from(&value))?
+                .map(|key| key.into_owned());
+            match indexed_key {
+                Some(indexed_key) if indexed_key != key => {
+                    self.violations.push(RegistryViolation::WrongIndex {
+                        table,
+                        key: key.as_u32(),
+                        indexed_key: indexed_key.as_u32(),
+                    })
+                }
+                Some(_) => {}
+                None => self.violations.push(RegistryViolation::Unindexed {
+                    table,
+                    key: key.as_u32(),
+                }),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Print the report
+    pub fn print(&self) {
+        for violation in &self.violations {
+            println!("{violation}");
+        }
+        println!(
+            "Checked {} registry entry(ies) and {} index entry(ies), found {} problem(s)",
+            self.entries,
+            self.indexes,
+            self.violations.len()
+        );
+    }
+}
+
+/// Whether the entry of `T` under `key` still holds the value indexed by
+/// `reverse_key`, `None` when there is no entry
+fn index_matches<T>(
+    compression: &FuelDatabase<CompressionDatabase>,
+    key: &RegistryKey,
+    reverse_key: &ReverseKey,
+) -> anyhow::Result<Option<bool>>
+where
+    T: Mappable<Key = RegistryKey>,
+    for<'a> ReverseKey: From<&'a T::OwnedValue>,
+    FuelDatabase<CompressionDatabase>: StorageInspect<T, Error = StorageError>,
+{
+    Ok(compression
+        .storage::<T>()
+        .get(key)?
+        .map(|value| ReverseKey::from(value.as_ref()) == *reverse_key))
+}
@@ -0,0 +1,273 @@
+//! Track the forced transactions of the relayer history through their relayed status
+//! and on-chain inclusion
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::{
+        relayed_transactions::RelayedTransactionStatuses,
+        transactions::TransactionStatuses,
+    },
+    types::{
+        blockchain::primitives::DaBlockHeight,
+        entities::relayer::transaction::{
+            RelayedTransactionId,
+            RelayedTransactionStatus,
+        },
+        fuel_tx::{
+            Transaction,
+            TxId,
+            UniqueIdentifier,
+        },
+        fuel_types::{
+            BlockHeight,
+            canonical::Deserialize,
+        },
+        services::{
+            relayer::Event,
+            transaction_status::TransactionExecutionStatus,
+        },
+    },
+};
+use fuel_core_relayer::storage::EventsHistory;
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::{
+        IterDirection,
+        IteratorOverTable,
+    },
+    tables::ProcessedTransactions,
+};
+
+use crate::{
+    chain::{
+        chain_id,
+        latest_block_da_height,
+    },
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+};
+
+/// Relayed transactions command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct RelayedTransactionsArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// First DA height to track, defaults to the first recorded one
+    #[arg(long)]
+    from: Option<u64>,
+
+    /// Last DA height to track, defaults to the latest recorded one
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// Only list the processed forced transactions that were neither included nor failed
+    #[arg(long)]
+    missing: bool,
+}
+
+impl RelayedTransactionsArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the relayed transactions command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::Relayer,
+            &[Database::OnChain, Database::OffChain],
+            self.database_config.clone(),
+        )?;
+
+        let result =
+            RelayedTransactionsReport::compute(&database_handle, self.from, self.to)
+                .and_then(|mut report| {
+                    if self.missing {
+                        report.transactions.retain(|transaction| {
+                            matches!(transaction.status, ForcedTransactionStatus::Missing)
+                        });
+                    }
+                    report.print_json()
+                });
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// Where a forced transaction ended up
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ForcedTransactionStatus {
+    /// The transaction was included in a block
+    Included {
+        /// height of the block, if `TransactionStatuses` records it
+        block_height: Option<BlockHeight>,
+    },
+    /// The transaction was rejected while being relayed
+    Failed {
+        /// height of the block that rejected it
+        block_height: BlockHeight,
+        /// reason stored in `RelayedTransactionStatuses`
+        failure: String,
+    },
+    /// The DA height is above the one of the latest block, so no block processed it
+    /// yet
+    Pending,
+    /// A block processed the DA height, but the transaction was neither included nor
+    /// recorded as failed
+    Missing,
+}
+
+/// A forced transaction of the relayer history
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ForcedTransactionEntry {
+    /// DA height the transaction was relayed at
+    pub da_height: DaBlockHeight,
+    /// id of the relayed transaction
+    pub relayed_id: RelayedTransactionId,
+    /// id of the decoded transaction, if the serialized bytes decode
+    pub tx_id: Option<TxId>,
+    /// gas claimed on the DA layer
+    pub max_gas: u64,
+    /// where the transaction ended up
+    #[serde(flatten)]
+    pub status: ForcedTransactionStatus,
+}
+
+/// Forced transactions over a DA height range
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct RelayedTransactionsReport {
+    /// DA height referenced by the latest block
+    pub processed_da_height: DaBlockHeight,
+    /// number of included transactions
+    pub included: usize,
+    /// number of failed transactions
+    pub failed: usize,
+    /// number of pending transactions
+    pub pending: usize,
+    /// number of missing transactions
+    pub missing: usize,
+    /// every forced transaction in the range
+    pub transactions: Vec<ForcedTransactionEntry>,
+}
+
+impl RelayedTransactionsReport {
+    /// Join the forced transactions recorded in `History` between `from` and `to`
+    /// with `RelayedTransactionStatuses` and `ProcessedTransactions`
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> anyhow::Result<Self> {
+        let relayer = database_handle.database().relayer();
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let chain_id = chain_id(database_handle)?;
+        let mut report = Self {
+            processed_da_height: latest_block_da_height(database_handle)?,
+            ..Default::default()
+        };
+
+        let start = from.map(DaBlockHeight::from);
+        for entry in relayer.iter_all_filtered::<EventsHistory, [u8; 0]>(
+            None,
+            start.as_ref(),
+            Some(IterDirection::Forward),
+        ) {
+            let (da_height, events) = entry?;
+            if to.is_some_and(|to| *da_height > to) {
+                break;
+            }
+
+            for event in events {
+                let Event::Transaction(transaction) = event else {
+                    continue;
+                };
+                let relayed_id = transaction.id();
+                let tx_id = Transaction::from_bytes(transaction.serialized_transaction())
+                    .ok()
+                    .map(|tx| tx.id(&chain_id));
+
+                let relayed_status = off_chain
+                    .storage::<RelayedTransactionStatuses>()
+                    .get(&relayed_id.clone().into())?
+                    .map(|status| status.into_owned());
+                let included = match tx_id {
+                    Some(tx_id) => on_chain
+                        .storage::<ProcessedTransactions>()
+                        .contains_key(&tx_id)?,
+                    None => false,
+                };
+
+                let status = match relayed_status {
+                    Some(RelayedTransactionStatus::Failed {
+                        block_height,
+                        failure,
+                    }) => {
+                        report.failed = report.failed.saturating_add(1);
+                        ForcedTransactionStatus::Failed {
+                            block_height,
+                            failure,
+                        }
+                    }
+                    None if included => {
+                        report.included = report.included.saturating_add(1);
+                        ForcedTransactionStatus::Included {
+                            block_height: inclusion_height(database_handle, tx_id)?,
+                        }
+                    }
+                    None if da_height > report.processed_da_height => {
+                        report.pending = report.pending.saturating_add(1);
+                        ForcedTransactionStatus::Pending
+                    }
+                    None => {
+                        report.missing = report.missing.saturating_add(1);
+                        ForcedTransactionStatus::Missing
+                    }
+                };
+
+                report.transactions.push(ForcedTransactionEntry {
+                    da_height,
+                    relayed_id,
+                    tx_id,
+                    max_gas: transaction.max_gas(),
+                    status,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Height of the block that included `tx_id`, as recorded in `TransactionStatuses`
+fn inclusion_height(
+    database_handle: &DatabaseHandle,
+    tx_id: Option<TxId>,
+) -> anyhow::Result<Option<BlockHeight>> {
+    let Some(tx_id) = tx_id else {
+        return Ok(None);
+    };
+    let status = database_handle
+        .database()
+        .off_chain()
+        .storage::<TransactionStatuses>()
+        .get(&tx_id)?
+        .map(|status| status.into_owned());
+
+    Ok(match status {
+        Some(
+            TransactionExecutionStatus::Success { block_height, .. }
+            | TransactionExecutionStatus::Failed { block_height, .. },
+        ) => Some(block_height),
+        _ => None,
+    })
+}
@@ -0,0 +1,188 @@
+//! Decode the relayer events history per DA height
+
+use fuel_core::types::{
+    blockchain::primitives::DaBlockHeight,
+    entities::relayer::transaction::RelayedTransactionId,
+    fuel_types::Nonce,
+    services::relayer::Event,
+};
+use fuel_core_relayer::storage::EventsHistory;
+use fuel_core_storage::iter::{
+    IterDirection,
+    IteratorOverTable,
+};
+
+use crate::{
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+};
+
+/// Relayer command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct RelayerArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Only decode the events of this DA height
+    #[arg(long, conflicts_with_all = ["from", "to"])]
+    da_height: Option<u64>,
+
+    /// First DA height to decode, defaults to the first recorded one
+    #[arg(long)]
+    from: Option<u64>,
+
+    /// Last DA height to decode, defaults to the latest recorded one
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// Include the decoded events in the output
+    #[arg(long)]
+    events: bool,
+}
+
+impl RelayerArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the relayer command
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle =
+            DatabaseHandle::try_new(Database::Relayer, self.database_config.clone())?;
+
+        let (from, to) = match self.da_height {
+            Some(da_height) => (Some(da_height), Some(da_height)),
+            None => (self.from, self.to),
+        };
+
+        let result = RelayerReport::compute(&database_handle, from, to, self.events)
+            .and_then(|report| {
+                if report.heights.is_empty() {
+                    if let Some(da_height) = self.da_height {
+                        anyhow::bail!(
+                            "No relayer events recorded at DA height {da_height}"
+                        );
+                    }
+                }
+                report.print_json()
+            });
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// Summary of the events recorded at a single DA height
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DaHeightEvents {
+    /// the DA height
+    pub da_height: DaBlockHeight,
+    /// number of message events
+    pub messages: usize,
+    /// total amount of the messages
+    pub message_amount: u128,
+    /// nonces of the messages
+    pub nonces: Vec<Nonce>,
+    /// ids of the forced transactions
+    pub forced_transactions: Vec<RelayedTransactionId>,
+    /// the decoded events, only if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<Event>>,
+}
+
+impl DaHeightEvents {
+    /// Summarize the `events` recorded at `da_height`
+    pub fn new(da_height: DaBlockHeight, events: Vec<Event>, keep_events: bool) -> Self {
+        let mut summary = Self {
+            da_height,
+            messages: 0,
+            message_amount: 0,
+            nonces: vec![],
+            forced_transactions: vec![],
+            events: None,
+        };
+
+        for event in &events {
+            match event {
+                Event::Message(message) => {
+                    summary.messages = summary.messages.saturating_add(1);
+                    summary.message_amount = summary
+                        .message_amount
+                        .saturating_add(u128::from(message.amount()));
+                    summary.nonces.push(*message.nonce());
+                }
+                Event::Transaction(transaction) => {
+                    summary.forced_transactions.push(transaction.id());
+                }
+            }
+        }
+
+        if keep_events {
+            summary.events = Some(events);
+        }
+
+        summary
+    }
+}
+
+/// Decoded relayer history over a DA height range
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RelayerReport {
+    /// finalized DA height recorded in the relayer `Metadata`
+    pub finalized_da_height: Option<DaBlockHeight>,
+    /// total number of message events in the range
+    pub messages: usize,
+    /// total amount of the messages in the range
+    pub message_amount: u128,
+    /// total number of forced transactions in the range
+    pub forced_transactions: usize,
+    /// events per DA height
+    pub heights: Vec<DaHeightEvents>,
+}
+
+impl RelayerReport {
+    /// Decode the `History` entries between `from` and `to` and read the finalized
+    /// DA height
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        from: Option<u64>,
+        to: Option<u64>,
+        keep_events: bool,
+    ) -> anyhow::Result<Self> {
+        let relayer = database_handle.database().relayer();
+
+        let start = from.map(DaBlockHeight::from);
+        let mut heights = vec![];
+        for entry in relayer.iter_all_filtered::<EventsHistory, [u8; 0]>(
+            None,
+            start.as_ref(),
+            Some(IterDirection::Forward),
+        ) {
+            let (da_height, events) = entry?;
+            if to.is_some_and(|to| *da_height > to) {
+                break;
+            }
+            heights.push(DaHeightEvents::new(da_height, events, keep_events));
+        }
+
+        Ok(Self {
+            finalized_da_height: relayer.latest_height_from_metadata()?,
+            messages: heights.iter().map(|height| height.messages).sum(),
+            message_amount: heights.iter().fold(0u128, |total, height| {
+                total.saturating_add(height.message_amount)
+            }),
+            forced_transactions: heights
+                .iter()
+                .map(|height| height.forced_transactions.len())
+                .sum(),
+            heights,
+        })
+    }
+}
@@ -0,0 +1,151 @@
+//! Inventory of the state transition bytecode versions and their uploads
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use fuel_core::types::{
+    blockchain::header::{
+        BlockHeader,
+        StateTransitionBytecodeVersion,
+    },
+    fuel_types::{
+        BlockHeight,
+        Bytes32,
+    },
+    fuel_vm::UploadedBytecode,
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    iter::IteratorOverTable,
+    tables::{
+        StateTransitionBytecodeVersions,
+        UploadedBytecodes,
+    },
+};
+
+use crate::{
+    chain::first_heights,
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+};
+
+/// State transition bytecodes command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct StateTransitionBytecodesArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Only report this version
+    #[arg(long)]
+    version: Option<StateTransitionBytecodeVersion>,
+
+    /// Write the WASM of every complete upload to `<version>-<root>.wasm` in this directory
+    #[arg(long)]
+    export_dir: Option<PathBuf>,
+}
+
+impl StateTransitionBytecodesArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the state transition bytecodes inventory
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OnChain,
+            &[Database::OffChain],
+            self.database_config.clone(),
+        )?;
+
+        let result = self.inventory(&database_handle);
+        database_handle.shutdown();
+
+        result
+    }
+
+    fn inventory(&self, database_handle: &DatabaseHandle) -> anyhow::Result<()> {
+        let first_heights = first_heights(
+            database_handle,
+            BlockHeader::state_transition_bytecode_version,
+        )?;
+
+        let on_chain = database_handle.database().on_chain();
+        let mut entries = vec![];
+        for entry in on_chain.iter_all::<StateTransitionBytecodeVersions>(None) {
+            let (version, root) = entry?;
+            if self.version.is_some_and(|v| v != version) {
+                continue;
+            }
+
+            let upload = on_chain
+                .storage::<UploadedBytecodes>()
+                .get(&root)?
+                .map(|upload| upload.into_owned());
+
+            let (complete, uploaded_subsections, bytecode) = match upload {
+                Some(UploadedBytecode::Completed(bytecode)) => {
+                    (true, None, Some(bytecode))
+                }
+                Some(UploadedBytecode::Uncompleted {
+                    bytecode,
+                    uploaded_subsections_number,
+                }) => (false, Some(uploaded_subsections_number), Some(bytecode)),
+                None => (false, None, None),
+            };
+
+            let exported = match (&self.export_dir, complete, &bytecode) {
+                (Some(dir), true, Some(bytecode)) => {
+                    let path = dir.join(format!("{version}-{root}.wasm"));
+                    std::fs::write(&path, bytecode)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    Some(path)
+                }
+                _ => None,
+            };
+
+            entries.push(StateTransitionBytecodeEntry {
+                version,
+                root,
+                uploaded: bytecode.is_some(),
+                complete,
+                uploaded_subsections,
+                size: bytecode.as_ref().map(Vec::len),
+                first_height: first_heights.get(&version).copied(),
+                exported,
+            });
+        }
+
+        if let (Some(version), true) = (self.version, entries.is_empty()) {
+            anyhow::bail!("State transition bytecode version {version} not found");
+        }
+
+        entries.print_json()
+    }
+}
+
+/// A state transition bytecode version and the state of its upload
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StateTransitionBytecodeEntry {
+    /// version of the state transition bytecode
+    pub version: StateTransitionBytecodeVersion,
+    /// bytecode root the version maps to
+    pub root: Bytes32,
+    /// whether `UploadedBytecodes` has an entry for the root
+    pub uploaded: bool,
+    /// whether every subsection was uploaded
+    pub complete: bool,
+    /// number of uploaded subsections of an incomplete upload
+    pub uploaded_subsections: Option<u16>,
+    /// size of the uploaded bytecode in bytes
+    pub size: Option<usize>,
+    /// height of the first stored block using this version
+    pub first_height: Option<BlockHeight>,
+    /// file the WASM was exported to
+    pub exported: Option<PathBuf>,
+}
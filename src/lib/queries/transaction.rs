@@ -0,0 +1,180 @@
+//! Look up a transaction by id across the on-chain and off-chain databases
+
+use fuel_core::{
+    fuel_core_graphql_api::storage::{
+        old::{
+            OldFuelBlocks,
+            OldTransactions,
+        },
+        relayed_transactions::RelayedTransactionStatuses,
+        transactions::TransactionStatuses,
+    },
+    types::{
+        blockchain::primitives::BlockId,
+        entities::relayer::transaction::RelayedTransactionStatus,
+        fuel_tx::{
+            Transaction,
+            TxId,
+        },
+        fuel_types::BlockHeight,
+        services::transaction_status::TransactionExecutionStatus,
+    },
+};
+use fuel_core_storage::{
+    StorageAsRef,
+    tables::{
+        FuelBlocks,
+        ProcessedTransactions,
+        Transactions,
+    },
+};
+
+use crate::{
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+    printer::PrintJsonExt,
+};
+
+/// Transaction lookup command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct TransactionArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Id of the transaction
+    id: TxId,
+}
+
+impl TransactionArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the transaction lookup
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_with(
+            Database::OffChain,
+            &[Database::OnChain],
+            self.database_config.clone(),
+        )?;
+
+        let lookup = TransactionLookup::find(&database_handle, self.id)?;
+        database_handle.shutdown();
+
+        if !lookup.found() {
+            anyhow::bail!("Transaction {} not found in any database", self.id);
+        }
+
+        lookup.print_json()
+    }
+}
+
+/// Block that includes a transaction
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct IncludingBlock {
+    /// height of the block
+    pub height: BlockHeight,
+    /// id of the block
+    pub id: BlockId,
+    /// table the block is stored in
+    pub table: &'static str,
+}
+
+/// Everything known about a transaction
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TransactionLookup {
+    /// id of the transaction
+    pub id: TxId,
+    /// whether the transaction is recorded in `ProcessedTransactions`
+    pub processed: bool,
+    /// table the transaction body is stored in
+    pub table: Option<&'static str>,
+    /// the decoded transaction
+    pub transaction: Option<Transaction>,
+    /// execution status of the transaction, including its receipts
+    pub status: Option<TransactionExecutionStatus>,
+    /// block the status points at
+    pub block: Option<IncludingBlock>,
+    /// status of the relayed transaction with this id
+    pub relayed_status: Option<RelayedTransactionStatus>,
+}
+
+impl TransactionLookup {
+    /// Collect the entries for `id` from `Transactions`, `ProcessedTransactions`,
+    /// `TransactionStatuses`, `OldTransactions` and `RelayedTransactionStatuses`
+    pub fn find(database_handle: &DatabaseHandle, id: TxId) -> anyhow::Result<Self> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let processed = on_chain
+            .storage::<ProcessedTransactions>()
+            .contains_key(&id)?;
+
+        let (table, transaction) = match on_chain.storage::<Transactions>().get(&id)? {
+            Some(tx) => (Some("Transactions"), Some(tx.into_owned())),
+            None => match off_chain.storage::<OldTransactions>().get(&id)? {
+                Some(tx) => (Some("OldTransactions"), Some(tx.into_owned())),
+                None => (None, None),
+            },
+        };
+
+        let status = off_chain
+            .storage::<TransactionStatuses>()
+            .get(&id)?
+            .map(|status| status.into_owned());
+
+        let block = match &status {
+            Some(
+                TransactionExecutionStatus::Success { block_height, .. }
+                | TransactionExecutionStatus::Failed { block_height, .. },
+            ) => Self::block_at(database_handle, *block_height)?,
+            _ => None,
+        };
+
+        let relayed_status = off_chain
+            .storage::<RelayedTransactionStatuses>()
+            .get(&id)?
+            .map(|status| status.into_owned());
+
+        Ok(Self {
+            id,
+            processed,
+            table,
+            transaction,
+            status,
+            block,
+            relayed_status,
+        })
+    }
+
+    fn block_at(
+        database_handle: &DatabaseHandle,
+        height: BlockHeight,
+    ) -> anyhow::Result<Option<IncludingBlock>> {
+        let on_chain = database_handle.database().on_chain();
+        let off_chain = database_handle.database().off_chain();
+
+        let block = match on_chain.storage::<FuelBlocks>().get(&height)? {
+            Some(block) => Some((block.header().id(), "FuelBlocks")),
+            None => off_chain
+                .storage::<OldFuelBlocks>()
+                .get(&height)?
+                .map(|block| (block.header().id(), "OldFuelBlocks")),
+        };
+
+        Ok(block.map(|(id, table)| IncludingBlock { height, id, table }))
+    }
+
+    /// Whether any database knows about the transaction
+    pub fn found(&self) -> bool {
+        self.processed
+            || self.transaction.is_some()
+            || self.status.is_some()
+            || self.relayed_status.is_some()
+    }
+}
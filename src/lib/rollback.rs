@@ -0,0 +1,726 @@
+//! Roll a database back to an earlier block height
+
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+
+use fuel_core::{
+    database::{
+        Database as FuelDatabase,
+        database_description::{
+            DatabaseDescription,
+            DatabaseMetadata,
+        },
+        metadata::MetadataTable,
+    },
+    fuel_core_graphql_api::storage::{
+        blocks::FuelBlockIdsToHeights,
+        old::{
+            OldFuelBlockConsensus,
+            OldFuelBlocks,
+        },
+        transactions::{
+            OwnedTransactions,
+            TransactionStatuses,
+        },
+    },
+    types::{
+        fuel_types::BlockHeight,
+        services::transaction_status::TransactionExecutionStatus,
+    },
+};
+use fuel_core_compression_service::storage::CompressedBlocks;
+use fuel_core_gas_price_service::common::fuel_core_storage_adapter::storage::{
+    GasPriceMetadata,
+    UnrecordedBlocksTable,
+};
+use fuel_core_storage::{
+    Error as StorageError,
+    Mappable,
+    StorageAsMut,
+    StorageAsRef,
+    StorageInspect,
+    StorageMutate,
+    iter::{
+        IterDirection,
+        IterableTable,
+        IteratorOverTable,
+    },
+    kv_store::StorageColumn,
+    transactional::{
+        Changes,
+        HistoricalView,
+        ReadTransaction,
+        StorageTransaction,
+    },
+};
+
+use crate::{
+    columns::{
+        CompressionColumn,
+        GasPriceColumn,
+        OffChainColumn,
+        OnchainColumn,
+    },
+    databases::{
+        Database,
+        DatabaseConfig,
+        DatabaseHandle,
+    },
+};
+
+/// Rollback command configuration
+#[derive(clap::Args, Debug, Clone)]
+pub struct RollbackArgs {
+    /// Database config
+    #[clap(flatten)]
+    database_config: DatabaseConfig,
+
+    /// Database to roll back
+    #[arg(long, value_enum)]
+    database: Database,
+
+    /// Block height to roll back to
+    #[arg(long)]
+    to: u32,
+
+    /// Only report the keys that would change
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Roll back without a complete modifications history by only removing the
+    /// entries keyed by the rolled back heights, leaving the other columns as they are
+    #[arg(long)]
+    force: bool,
+}
+
+impl RollbackArgs {
+    /// database config
+    pub fn database_config(&self) -> &DatabaseConfig {
+        &self.database_config
+    }
+
+    /// Run the rollback
+    pub fn run(&self) -> anyhow::Result<()> {
+        let database_handle = DatabaseHandle::try_new_historical(
+            self.database,
+            &[],
+            self.database_config.clone(),
+        )?;
+
+        let result =
+            RollbackReport::compute(&database_handle, self.to.into(), self.force)
+                .and_then(|report| {
+                    report.print(self.dry_run);
+                    if self.dry_run {
+                        return Ok(());
+                    }
+                    report.apply(&database_handle)
+                });
+
+        database_handle.shutdown();
+
+        result
+    }
+}
+
+/// Where the rolled back changes come from
+#[derive(Debug, Clone)]
+pub enum RollbackSource {
+    /// The reverse changes of the modifications history, replayed block by block
+    History,
+    /// The entries keyed by the rolled back heights, removed together with lowering
+    /// the metadata height
+    HeightKeyed {
+        /// removals of the height-keyed entries and the lowered metadata
+        changes: Changes,
+        /// names of the columns left as they are
+        unreverted: Vec<String>,
+    },
+}
+
+impl core::fmt::Display for RollbackSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::History => write!(f, "the modifications history"),
+            Self::HeightKeyed { .. } => write!(f, "the height-keyed entries"),
+        }
+    }
+}
+
+/// Number of keys a rollback changes in a column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnChanges {
+    /// name of the column
+    pub column: String,
+    /// number of changed keys
+    pub keys: usize,
+}
+
+/// Changes needed to roll a database back
+#[derive(Debug, Clone)]
+pub struct RollbackReport {
+    /// the rolled back database
+    pub database: Database,
+    /// height of the database before the rollback
+    pub height: BlockHeight,
+    /// height to roll back to
+    pub to: BlockHeight,
+    /// where the changes come from
+    pub source: RollbackSource,
+    /// changed keys per column
+    pub columns: Vec<ColumnChanges>,
+}
+
+impl RollbackReport {
+    /// Collect the changes that roll the database of `database_handle` back to `to`.
+    ///
+    /// The modifications history must cover every rolled back height. With `force`,
+    /// the off-chain, gas price and compression databases fall back to removing their
+    /// entries keyed by the rolled back heights, which leaves the other columns, like
+    /// balances or registry keys, as they are. They are listed in the report.
+    pub fn compute(
+        database_handle: &DatabaseHandle,
+        to: BlockHeight,
+        force: bool,
+    ) -> anyhow::Result<Self> {
+        let database = database_handle.variant();
+        let history = database_handle.modifications_history(database, (*to).into())?;
+        let combined = database_handle.database();
+
+        let (height, source, columns) = match database {
+            Database::OnChain => changes_for::<OnchainColumn, _, _>(
+                combined.on_chain(),
+                to,
+                history,
+                force,
+                None,
+                |_, _| Ok(()),
+            )?,
+            Database::OffChain => changes_for::<OffChainColumn, _, _>(
+                combined.off_chain(),
+                to,
+                history,
+                force,
+                Some(&[
+                    OffChainColumn::Metadata,
+                    OffChainColumn::OldFuelBlocks,
+                    OffChainColumn::OldFuelBlockConsensus,
+                    OffChainColumn::FuelBlockIdsToHeights,
+                    OffChainColumn::TransactionStatus,
+                    OffChainColumn::TransactionsByOwnerBlockIdx,
+                ]),
+                |off_chain, tx| {
+                    remove_above::<OldFuelBlocks, _>(off_chain, tx, to)?;
+                    remove_above::<OldFuelBlockConsensus, _>(off_chain, tx, to)?;
+
+                    let block_ids = off_chain
+                        .iter_all::<FuelBlockIdsToHeights>(None)
+                        .filter_map(|entry| match entry {
+                            Ok((block_id, height)) if height > to => Some(Ok(block_id)),
+                            Ok(_) => None,
+                            Err(error) => Some(Err(error)),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    for block_id in block_ids {
+                        tx.storage_as_mut::<FuelBlockIdsToHeights>()
+                            .remove(&block_id)?;
+                    }
+
+                    let tx_ids = off_chain
+                        .iter_all::<TransactionStatuses>(None)
+                        .filter_map(|entry| match entry {
+                            Ok((
+                                tx_id,
+                                TransactionExecutionStatus::Success {
+                                    block_height, ..
+                                }
+                                | TransactionExecutionStatus::Failed {
+                                    block_height, ..
+                                },
+                            )) if block_height > to => Some(Ok(tx_id)),
+                            Ok(_) => None,
+                            Err(error) => Some(Err(error)),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    for tx_id in tx_ids {
+                        tx.storage_as_mut::<TransactionStatuses>().remove(&tx_id)?;
+                    }
+
+                    let owned = off_chain
+                        .iter_all_keys::<OwnedTransactions>(None)
+                        .filter(|key| {
+                            !key.as_ref().is_ok_and(|key| key.block_height <= to)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    for key in owned {
+                        tx.storage_as_mut::<OwnedTransactions>().remove(&key)?;
+                    }
+
+                    Ok(())
+                },
+            )?,
+            Database::GasPrice => changes_for::<GasPriceColumn, _, _>(
+                combined.gas_price(),
+                to,
+                history,
+                force,
+                Some(&[
+                    GasPriceColumn::Metadata,
+                    GasPriceColumn::State,
+                    GasPriceColumn::UnrecordedBlocks,
+                ]),
+                |gas_price, tx| {
+                    remove_above::<GasPriceMetadata, _>(gas_price, tx, to)?;
+                    remove_above::<UnrecordedBlocksTable, _>(gas_price, tx, to)
+                },
+            )?,
+            Database::Compression => changes_for::<CompressionColumn, _, _>(
+                combined.compression(),
+                to,
+                history,
+                force,
+                Some(&[CompressionColumn::CompressedBlocks]),
+                |compression, tx| {
+                    remove_above::<CompressedBlocks, _>(compression, tx, to)
+                },
+            )?,
+            Database::Relayer => anyhow::bail!(
+                "The relayer database follows DA heights and can't be rolled back to a block height"
+            ),
+        };
+
+        Ok(Self {
+            database,
+            height,
+            to,
+            source,
+            columns,
+        })
+    }
+
+    /// Roll the database back
+    pub fn apply(&self, database_handle: &DatabaseHandle) -> anyhow::Result<()> {
+        let changes = match &self.source {
+            RollbackSource::History => {
+                let combined = database_handle.database();
+                return match self.database {
+                    Database::OnChain => rollback_history(combined.on_chain(), self.to),
+                    Database::OffChain => rollback_history(combined.off_chain(), self.to),
+                    Database::GasPrice => rollback_history(combined.gas_price(), self.to),
+                    Database::Compression => {
+                        rollback_history(combined.compression(), self.to)
+                    }
+                    Database::Relayer => {
+                        anyhow::bail!("The relayer database can't be rolled back")
+                    }
+                };
+            }
+            RollbackSource::HeightKeyed { changes, .. } => changes.clone(),
+        };
+
+        database_handle.commit_changes(self.database, changes)
+    }
+
+    /// Print the report
+    pub fn print(&self, dry_run: bool) {
+        for ColumnChanges { column, keys } in &self.columns {
+            println!("{column}: {keys} key(s)");
+        }
+        if let RollbackSource::HeightKeyed { unreverted, .. } = &self.source {
+            for column in unreverted {
+                println!("{column}: not reverted");
+            }
+            if self.database == Database::Compression {
+                println!(
+                    "Warning: the registry, EvictorCache and Timestamps stay ahead of height {}, so blocks compressed after the rollback will use other registry keys than the removed ones",
+                    *self.to
+                );
+            }
+        }
+        let keys = self
+            .columns
+            .iter()
+            .map(|column| column.keys)
+            .fold(0usize, usize::saturating_add);
+        println!(
+            "{} the {:?} database from height {} to {} with {}, changing {} key(s) in {} column(s)",
+            if dry_run {
+                "Would roll back"
+            } else {
+                "Rolling back"
+            },
+            self.database,
+            *self.height,
+            *self.to,
+            self.source,
+            keys,
+            self.columns.len()
+        );
+    }
+}
+
+/// Height, source and changed keys per column of rolling `database` back to `to`.
+/// When `history` doesn't cover every rolled back height and `force` is set,
+/// `height_keyed` removes the height-keyed entries of the `reverted` columns instead.
+/// Databases without `reverted` columns can't fall back.
+fn changes_for<'a, ColumnName, Description, F>(
+    database: &'a FuelDatabase<Description>,
+    to: BlockHeight,
+    history: BTreeMap<u64, Changes>,
+    force: bool,
+    reverted: Option<&[ColumnName]>,
+    height_keyed: F,
+) -> anyhow::Result<(BlockHeight, RollbackSource, Vec<ColumnChanges>)>
+where
+    ColumnName: enum_iterator::Sequence + PartialEq + Into<Description::Column>,
+    Description: DatabaseDescription<Height = BlockHeight>,
+    FuelDatabase<Description>:
+        StorageInspect<MetadataTable<Description>, Error = StorageError>,
+    F: FnOnce(
+        &'a FuelDatabase<Description>,
+        &mut StorageTransaction<&'a FuelDatabase<Description>>,
+    ) -> anyhow::Result<()>,
+{
+    let name = Description::name();
+    let height = database
+        .latest_height()
+        .ok_or_else(|| anyhow::anyhow!("The {name} database has no height"))?;
+    if height < to {
+        anyhow::bail!(
+            "The {name} database is at height {}, below the target height {}",
+            *height,
+            *to
+        );
+    }
+
+    let rolled_back =
+        (u64::from(*to)..u64::from(*height)).map(|height| height.saturating_add(1));
+    if rolled_back
+        .clone()
+        .all(|height| history.contains_key(&height))
+    {
+        let columns = count_keys::<ColumnName, Description>(history.values());
+        return Ok((height, RollbackSource::History, columns));
+    }
+    let Some(reverted) = reverted else {
+        anyhow::bail!(
+            "The modifications history of the {name} database doesn't cover every height from {} to {}, and it can't be rolled back without it",
+            to.saturating_add(1),
+            *height
+        );
+    };
+    let unreverted = enum_iterator::all::<ColumnName>()
+        .filter(|column| !reverted.contains(column))
+        .map(|column| Into::<Description::Column>::into(column).name())
+        .collect::<Vec<_>>();
+    if !force {
+        anyhow::bail!(
+            "The modifications history of the {name} database doesn't cover every height from {} to {}. Pass --force to only remove the entries keyed by height, which leaves {} as they are",
+            to.saturating_add(1),
+            *height,
+            unreverted.join(", ")
+        );
+    }
+
+    let metadata = database
+        .storage::<MetadataTable<Description>>()
+        .get(&())?
+        .ok_or_else(|| anyhow::anyhow!("The {name} database has no metadata"))?
+        .into_owned();
+    let metadata = match metadata {
+        DatabaseMetadata::V1 { version, .. } => DatabaseMetadata::V1 {
+            version,
+            height: to,
+        },
+        DatabaseMetadata::V2 {
+            version,
+            indexation_availability,
+            ..
+        } => DatabaseMetadata::V2 {
+            version,
+            height: to,
+            indexation_availability,
+        },
+    };
+
+    let mut tx = database.read_transaction();
+    height_keyed(database, &mut tx)?;
+    tx.storage_as_mut::<MetadataTable<Description>>()
+        .insert(&(), &metadata)?;
+    let changes = tx.into_changes();
+
+    let columns = count_keys::<ColumnName, Description>([&changes]);
+    Ok((
+        height,
+        RollbackSource::HeightKeyed {
+            changes,
+            unreverted,
+        },
+        columns,
+    ))
+}
+
+/// Removes the entries of `M` keyed by a height above `to`, the transaction can't be
+/// iterated so the keys are read from `database`
+fn remove_above<M, S>(
+    database: S,
+    tx: &mut StorageTransaction<S>,
+    to: BlockHeight,
+) -> anyhow::Result<()>
+where
+    M: Mappable<Key = BlockHeight, OwnedKey = BlockHeight>,
+    S: IterableTable<M>,
+    StorageTransaction<S>: StorageMutate<M, Error = StorageError>,
+{
+    let Some(start) = to.succ() else {
+        return Ok(());
+    };
+    let heights = database
+        .iter_all_filtered_keys::<M, [u8; 0]>(
+            None,
+            Some(&start),
+            Some(IterDirection::Forward),
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+    for height in heights {
+        tx.storage_as_mut::<M>().remove(&height)?;
+    }
+
+    Ok(())
+}
+
+/// Number of distinct keys per column of `changes`, the columns are named after the
+/// `ColumnName` or the metadata column with the same id
+fn count_keys<'a, ColumnName, Description>(
+    changes: impl IntoIterator<Item = &'a Changes>,
+) -> Vec<ColumnChanges>
+where
+    ColumnName: enum_iterator::Sequence + Into<Description::Column>,
+    Description: DatabaseDescription,
+{
+    let mut keys = BTreeMap::<u32, BTreeSet<_>>::new();
+    for changes in changes {
+        for (column, column_changes) in changes {
+            keys.entry(*column)
+                .or_default()
+                .extend(column_changes.keys().cloned());
+        }
+    }
+
+    keys.into_iter()
+        .map(|(id, keys)| ColumnChanges {
+            column: enum_iterator::all::<ColumnName>()
+                .map(Into::<Description::Column>::into)
+                .chain(core::iter::once(Description::metadata_column()))
+                .find(|column| column.id() == id)
+                .map_or_else(|| format!("column {id}"), |column| column.name()),
+            keys: keys.len(),
+        })
+        .collect()
+}
+
+/// Replays the modifications history of `database` until it is back at `to`
+fn rollback_history<Description>(
+    database: &FuelDatabase<Description>,
+    to: BlockHeight,
+) -> anyhow::Result<()>
+where
+    Description: DatabaseDescription<Height = BlockHeight>,
+    FuelDatabase<Description>:
+        StorageInspect<MetadataTable<Description>, Error = StorageError>,
+{
+    while database.latest_height().is_some_and(|height| height > to) {
+        database.rollback_last_block()?;
+    }
+
+    let height = database.latest_height_from_metadata()?;
+    if height != Some(to) {
+        anyhow::bail!(
+            "The {} database metadata is at height {:?} after the rollback to {}",
+            Description::name(),
+            height.map(|height| *height),
+            *to
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_core::{
+        database::database_description::compression::CompressionDatabase,
+        types::{
+            blockchain::block::CompressedBlock,
+            fuel_compression::RegistryKey,
+            fuel_tx::ContractId,
+            fuel_vm::Contract,
+            tai64::Tai64,
+        },
+    };
+    use fuel_core_compression::VersionedCompressedBlock;
+    use fuel_core_compression_service::storage::{
+        Timestamps,
+        timestamps::{
+            TimestampKey,
+            TimestampKeyspace,
+        },
+    };
+    use fuel_core_storage::{
+        tables::{
+            ContractsRawCode,
+            FuelBlocks,
+        },
+        transactional::{
+            IntoTransaction,
+            Modifiable,
+        },
+    };
+
+    use super::*;
+
+    fn open(database: Database, dir: &tempfile::TempDir) -> DatabaseHandle {
+        DatabaseHandle::try_new_historical(
+            database,
+            &[],
+            DatabaseConfig {
+                path: dir.path().to_string_lossy().into_owned(),
+            },
+        )
+        .unwrap()
+    }
+
+    fn keys(report: &RollbackReport, column: &str) -> Option<usize> {
+        report
+            .columns
+            .iter()
+            .find(|changes| changes.column == column)
+            .map(|changes| changes.keys)
+    }
+
+    #[test]
+    fn rollback_replays_the_modifications_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let database_handle = open(Database::OnChain, &dir);
+
+        let mut on_chain = database_handle.database().on_chain().clone();
+        for height in 0..4u8 {
+            let mut tx = on_chain.clone().into_transaction();
+            let mut block = CompressedBlock::default();
+            block
+                .header_mut()
+                .set_block_height(u32::from(height).into());
+            tx.storage_as_mut::<FuelBlocks>()
+                .insert(&u32::from(height).into(), &block)
+                .unwrap();
+            tx.storage_as_mut::<ContractsRawCode>()
+                .insert(&ContractId::zeroed(), &[height; 4])
+                .unwrap();
+            on_chain.commit_changes(tx.into_changes()).unwrap();
+        }
+
+        let report = RollbackReport::compute(&database_handle, 1.into(), false).unwrap();
+        assert!(matches!(report.source, RollbackSource::History));
+        assert_eq!(report.height, 3.into());
+        assert_eq!(keys(&report, "FuelBlocks"), Some(2));
+        assert_eq!(keys(&report, "ContractsRawCode"), Some(1));
+
+        report.apply(&database_handle).unwrap();
+
+        let on_chain = database_handle.database().on_chain();
+        assert_eq!(
+            on_chain.latest_height_from_metadata().unwrap(),
+            Some(1.into())
+        );
+        for height in 0..4u32 {
+            assert_eq!(
+                on_chain
+                    .storage::<FuelBlocks>()
+                    .contains_key(&height.into())
+                    .unwrap(),
+                height <= 1
+            );
+        }
+        assert_eq!(
+            on_chain
+                .storage::<ContractsRawCode>()
+                .get(&ContractId::zeroed())
+                .unwrap()
+                .map(|code| code.into_owned()),
+            Some(Contract::from(vec![1; 4]))
+        );
+
+        database_handle.shutdown();
+    }
+
+    #[test]
+    fn rollback_without_history_needs_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let timestamp_key = TimestampKey {
+            keyspace: TimestampKeyspace::Address,
+            key: RegistryKey::ZERO,
+        };
+
+        let database_handle = open(Database::Compression, &dir);
+        let mut tx = database_handle.database().compression().read_transaction();
+        for height in 0..4u32 {
+            tx.storage_as_mut::<CompressedBlocks>()
+                .insert(&height.into(), &VersionedCompressedBlock::default())
+                .unwrap();
+        }
+        tx.storage_as_mut::<Timestamps>()
+            .insert(&timestamp_key, &Tai64::UNIX_EPOCH)
+            .unwrap();
+        tx.storage_as_mut::<MetadataTable<CompressionDatabase>>()
+            .insert(
+                &(),
+                &DatabaseMetadata::V1 {
+                    version: CompressionDatabase::version(),
+                    height: 3.into(),
+                },
+            )
+            .unwrap();
+        database_handle
+            .commit_changes(Database::Compression, tx.into_changes())
+            .unwrap();
+        database_handle.shutdown();
+
+        let database_handle = open(Database::Compression, &dir);
+        assert!(RollbackReport::compute(&database_handle, 1.into(), false).is_err());
+
+        let report = RollbackReport::compute(&database_handle, 1.into(), true).unwrap();
+        let RollbackSource::HeightKeyed { unreverted, .. } = &report.source else {
+            panic!("expected the height-keyed fallback");
+        };
+        assert!(unreverted.iter().any(|column| column == "Timestamps"));
+        assert!(!unreverted.iter().any(|column| column == "CompressedBlocks"));
+        assert_eq!(keys(&report, "CompressedBlocks"), Some(2));
+
+        report.apply(&database_handle).unwrap();
+
+        let compression = database_handle.database().compression();
+        assert_eq!(
+            compression.latest_height_from_metadata().unwrap(),
+            Some(1.into())
+        );
+        for height in 0..4u32 {
+            assert_eq!(
+                compression
+                    .storage::<CompressedBlocks>()
+                    .contains_key(&height.into())
+                    .unwrap(),
+                height <= 1
+            );
+        }
+        assert!(
+            compression
+                .storage::<Timestamps>()
+                .contains_key(&timestamp_key)
+                .unwrap()
+        );
+
+        database_handle.shutdown();
+    }
+}